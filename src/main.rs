@@ -1,9 +1,18 @@
 use std::{io, vec};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
 use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::num::ParseFloatError;
+use std::ops::{Add, AddAssign, Div, Index, Mul, Neg, Sub};
 use std::path::Path;
-use csv::Writer;
+use std::str::FromStr;
+use csv::{ReaderBuilder, Writer};
+use num_bigint::BigUint;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 struct Vector{
     x: f64,
     y: f64,
@@ -26,61 +35,568 @@ impl Vector {
     fn cross_product(&self, other: &Vector) -> Vector {
         Vector::new(
             self.y * other.z - self.z * other.y,
-            self.x * other.z - self.z * other.x,
+            self.z * other.x - self.x * other.z,
             self.x * other.y - self.y * other.x,
         )
     }
+    #[allow(dead_code)]
     fn display(&self) {
-        println!("Vector: ({}, {}, {})", self.x, self.y, self.z);
+        println!("Vector: {}", self);
+    }
+
+    fn magnitude(&self) -> f64 {
+        self.dot_product(self).sqrt()
+    }
+
+    // Unit vector in the same direction
+    fn normalize(&self) -> Result<Vector, String> {
+        let magnitude = self.magnitude();
+        if magnitude == 0.0 {
+            return Err("The zero vector has no direction".to_string());
+        }
+        Ok(*self / magnitude)
+    }
+
+    // Angle in radians, between 0 and π
+    fn angle_between(&self, other: &Vector) -> Result<f64, String> {
+        let cosine = self.normalize()?.dot_product(&other.normalize()?);
+        // Rounding can push the cosine of (anti)parallel vectors just past ±1
+        Ok(cosine.clamp(-1.0, 1.0).acos())
+    }
+
+    fn angle_between_degrees(&self, other: &Vector) -> Result<f64, String> {
+        self.angle_between(other).map(f64::to_degrees)
+    }
+
+    // Component of this vector along the other one
+    fn project_onto(&self, other: &Vector) -> Result<Vector, String> {
+        let length_squared = other.dot_product(other);
+        if length_squared == 0.0 {
+            return Err("Cannot project onto the zero vector".to_string());
+        }
+        Ok(*other * (self.dot_product(other) / length_squared))
+    }
+
+    // Component of this vector perpendicular to the other one
+    fn reject_from(&self, other: &Vector) -> Result<Vector, String> {
+        Ok(*self - self.project_onto(other)?)
+    }
+
+    // Mirror image in the plane through the origin with the given normal, v - 2(v · n̂)n̂
+    fn reflect_across(&self, normal: &Vector) -> Result<Vector, String> {
+        Ok(*self - self.project_onto(normal)? * 2.0)
+    }
+
+    fn distance_to(&self, other: &Vector) -> f64 {
+        (*self - *other).magnitude()
+    }
+
+    // |a × b| ≤ tolerance · |a||b|; the zero vector is parallel to every vector
+    fn is_parallel(&self, other: &Vector, tolerance: f64) -> bool {
+        self.cross_product(other).magnitude() <= tolerance * self.magnitude() * other.magnitude()
+    }
+
+    // |a · b| ≤ tolerance · |a||b|; the zero vector is orthogonal to every vector
+    fn is_orthogonal(&self, other: &Vector, tolerance: f64) -> bool {
+        self.dot_product(other).abs() <= tolerance * self.magnitude() * other.magnitude()
+    }
+
+    // a · (b × c), the signed volume of the parallelepiped spanned by a, b and c
+    fn scalar_triple_product(&self, b: &Vector, c: &Vector) -> f64 {
+        self.dot_product(&b.cross_product(c))
+    }
+
+    // a × (b × c), which equals b(a · c) - c(a · b)
+    fn vector_triple_product(&self, b: &Vector, c: &Vector) -> Vector {
+        self.cross_product(&b.cross_product(c))
+    }
+
+    fn parallelepiped_volume(&self, b: &Vector, c: &Vector) -> f64 {
+        self.scalar_triple_product(b, c).abs()
+    }
+
+    // Volume of the tetrahedron with edges a, b and c from a common vertex
+    fn tetrahedron_volume(&self, b: &Vector, c: &Vector) -> f64 {
+        self.parallelepiped_volume(b, c) / 6.0
+    }
+
+    // Three vectors are coplanar when the parallelepiped they span is flat, i.e. when
+    // |a · (b × c)| ≤ tolerance · |a||b||c|. Four vectors are taken as points, which are
+    // coplanar when the edges from the first point to the other three are.
+    fn are_coplanar(vectors: &[Vector], tolerance: f64) -> Result<bool, String> {
+        let (a, b, c) = match vectors {
+            [a, b, c] => (*a, *b, *c),
+            [p, q, r, s] => (*q - *p, *r - *p, *s - *p),
+            _ => return Err(format!("Coplanarity needs 3 vectors or 4 points, found {}", vectors.len())),
+        };
+        Ok(a.parallelepiped_volume(&b, &c) <= tolerance * a.magnitude() * b.magnitude() * c.magnitude())
     }
 }
 
-fn read_vector() -> Vector {
-    println!("Enter x y z coordinates (space-separated):");
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).expect("Failed to read line");
+impl fmt::Display for Vector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
 
-    let coordinates: Vec<f64> = input
-        .trim()
-        .split_whitespace()
-        .map(|s| s.parse().expect("Invalid input"))
-        .collect();
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, other: Vector) -> Vector {
+        Vector::add(&self, &other)
+    }
+}
+
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, other: Vector) -> Vector {
+        self.substract(&other)
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl Mul<f64> for Vector {
+    type Output = Vector;
+
+    fn mul(self, scalar: f64) -> Vector {
+        Vector::new(self.x * scalar, self.y * scalar, self.z * scalar)
+    }
+}
+
+impl Div<f64> for Vector {
+    type Output = Vector;
+
+    fn div(self, scalar: f64) -> Vector {
+        Vector::new(self.x / scalar, self.y / scalar, self.z / scalar)
+    }
+}
+
+impl AddAssign for Vector {
+    fn add_assign(&mut self, other: Vector) {
+        *self = *self + other;
+    }
+}
+
+// Components by position: 0 is x, 1 is y and 2 is z
+impl Index<usize> for Vector {
+    type Output = f64;
+
+    fn index(&self, index: usize) -> &f64 {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("Vector index {} out of range for 3 components", index),
+        }
+    }
+}
 
-    if coordinates.len() != 3 {
-        panic!("Invalid number of coordinates provided");
+// Reads a three-dimensional vector, asking again until exactly three numbers are given
+fn read_vector() -> Vector {
+    loop {
+        match Vector::try_from(&read_vector_n()) {
+            Ok(vector) => return vector,
+            Err(e) => println!("{}. Please enter x y z.", e),
+        }
     }
+}
+
+// Reads the coordinates of a vector of any dimension, asking again until they are all numbers
+fn read_vector_n() -> VectorN {
+    loop {
+        println!("Enter the coordinates (space-separated, e.g. x y z):");
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).expect("Failed to read line");
 
-    Vector::new(coordinates[0], coordinates[1], coordinates[2])
+        let coordinates: Result<Vec<f64>, _> = input.split_whitespace().map(|s| s.parse()).collect();
+        match coordinates {
+            Ok(coordinates) if !coordinates.is_empty() => return VectorN::new(coordinates),
+            _ => println!("Invalid input. Please enter at least one number."),
+        }
+    }
 }
 
 fn vector_operations() -> Result<(), Box<dyn Error>>{
     println!("Enter your first vector values: ");
-    let vector1 = read_vector();
+    let vector_n1 = read_vector_n();
     println!("Enter your second vector values: ");
-    let vector2 = read_vector();
-    let result1 = vector1.add(&vector2);
-    let result2 = vector1.substract(&vector2);
+    let vector_n2 = read_vector_n();
+    let (vector1, vector2) = match (Vector::try_from(&vector_n1), Vector::try_from(&vector_n2)) {
+        (Ok(vector1), Ok(vector2)) => (vector1, vector2),
+        _ => return vector_n_operations(&vector_n1, &vector_n2),
+    };
+    let result1 = vector1 + vector2;
+    let result2 = vector1 - vector2;
     let result3 = vector1.dot_product(&vector2);
     let result4 = vector1.cross_product(&vector2);
-    println!("\nVector Addition:\n{:?}", result1);
-    println!("\nVector Substraction:\n{:?}", result2);
+    let result5 = -vector1;
+    println!("\nVector Addition:\n{}", result1);
+    println!("\nVector Substraction:\n{}", result2);
     println!("\nDot Product:\n{:?}", result3);
-    println!("\nCross Product:\n{:?}", result4);
+    println!("\nCross Product:\n{}", result4);
+    println!("\nNegation of the First Vector:\n{}", result5);
+
+    // Queries that are undefined for a zero vector are reported in place
+    let tolerance = 1e-9;
+    let show_vector = |result: Result<Vector, String>| result.map_or_else(|e| e, |v| v.to_string());
+    let show_angle = |result: Result<f64, String>| result.map_or_else(|e| e, |angle| angle.to_string());
+    let geometry = [
+        ("Magnitude of the First Vector", vector1.magnitude().to_string()),
+        ("Magnitude of the Second Vector", vector2.magnitude().to_string()),
+        ("Unit Vector of the First Vector", show_vector(vector1.normalize())),
+        ("Unit Vector of the Second Vector", show_vector(vector2.normalize())),
+        ("Angle Between (radians)", show_angle(vector1.angle_between(&vector2))),
+        ("Angle Between (degrees)", show_angle(vector1.angle_between_degrees(&vector2))),
+        ("Projection of the First onto the Second", show_vector(vector1.project_onto(&vector2))),
+        ("Rejection of the First from the Second", show_vector(vector1.reject_from(&vector2))),
+        ("Reflection of the First across the Second as a Normal", show_vector(vector1.reflect_across(&vector2))),
+        ("Distance Between", vector1.distance_to(&vector2).to_string()),
+        ("Parallel", vector1.is_parallel(&vector2, tolerance).to_string()),
+        ("Orthogonal", vector1.is_orthogonal(&vector2, tolerance).to_string()),
+    ];
+    for (name, result) in &geometry {
+        println!("\n{}:\n{}", name, result);
+    }
+
+    println!("\nCalculate triple products, volumes and coplanarity with a third vector? (true or false)");
+    let mut triple = Vec::new();
+    if read_boolean_input() {
+        println!("Enter your third vector values: ");
+        let vector3 = read_vector();
+        triple = vec![
+            ("Scalar Triple Product", vector1.scalar_triple_product(&vector2, &vector3).to_string()),
+            ("Vector Triple Product", vector1.vector_triple_product(&vector2, &vector3).to_string()),
+            ("Parallelepiped Volume", vector1.parallelepiped_volume(&vector2, &vector3).to_string()),
+            ("Tetrahedron Volume", vector1.tetrahedron_volume(&vector2, &vector3).to_string()),
+            ("Coplanar", Vector::are_coplanar(&[vector1, vector2, vector3], tolerance)?.to_string()),
+        ];
+        println!("\nAlso test whether the three vectors and a fourth one are coplanar as points? (true or false)");
+        if read_boolean_input() {
+            println!("Enter your fourth vector values: ");
+            let vector4 = read_vector();
+            let points = [vector1, vector2, vector3, vector4];
+            triple.push(("Coplanar as Four Points", Vector::are_coplanar(&points, tolerance)?.to_string()));
+        }
+        for (name, result) in &triple {
+            println!("\n{}:\n{}", name, result);
+        }
+    }
 
     let file_path = "vector_operations.csv";
     let mut writer = Writer::from_path(file_path)?;
 
-    writer.write_record(&["Vector Operation", "Result"])?;
-    writer.write_record(&["Vector Addition", &format!("[{:?} {:?} {:?}]", result1.x, result1.y, result1.z)])?;
-    writer.write_record(&["Vector Subtraction", &format!("[{:?} {:?} {:?}]", result2.x, result2.y, result2.z)])?;
-    writer.write_record(&["Dot Product", &format!("{}", result3)])?;
-    writer.write_record(&["Cross Product", &format!("[{:?} {:?} {:?}]", result4.x, result4.y, result4.z)])?;
+    writer.write_record(["Vector Operation", "Result"])?;
+    writer.write_record(["Vector Addition", &format!("[{:?} {:?} {:?}]", result1.x, result1.y, result1.z)])?;
+    writer.write_record(["Vector Subtraction", &format!("[{:?} {:?} {:?}]", result2.x, result2.y, result2.z)])?;
+    writer.write_record(["Dot Product", &format!("{}", result3)])?;
+    writer.write_record(["Cross Product", &format!("[{:?} {:?} {:?}]", result4.x, result4.y, result4.z)])?;
+    writer.write_record(["Negation of the First Vector", &result5.to_string()])?;
+    for (name, result) in geometry.iter().chain(&triple) {
+        writer.write_record([*name, result.as_str()])?;
+    }
 
     println!("Results have been written to {}", file_path);
 
     Ok(())
 }
 
+// Ways an operation on `VectorN`s can fail
+#[derive(Debug, Clone, PartialEq)]
+enum VectorError {
+    // The operands have different numbers of components
+    DimensionMismatch { expected: usize, found: usize },
+    // The operation only exists in some dimensions, such as the cross product in 3D and 7D
+    UnsupportedDimension { operation: &'static str, dimension: usize },
+    // The generalized cross product in n dimensions takes exactly n - 1 vectors
+    WrongVectorCount { expected: usize, found: usize },
+    // The operation needs a direction, which the zero vector does not have
+    ZeroVector,
+}
+
+impl fmt::Display for VectorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VectorError::DimensionMismatch { expected, found } => {
+                write!(f, "Dimension mismatch: expected {} components, found {}", expected, found)
+            }
+            VectorError::UnsupportedDimension { operation, dimension } => {
+                write!(f, "The {} is not defined in {} dimensions", operation, dimension)
+            }
+            VectorError::WrongVectorCount { expected, found } => write!(f, "Expected {} vectors, found {}", expected, found),
+            VectorError::ZeroVector => write!(f, "The zero vector has no direction"),
+        }
+    }
+}
+
+impl Error for VectorError {}
+
+// Vector with any number of components; the fixed-size `Vector` is the 3D case
+#[derive(Debug, Clone, PartialEq, Default)]
+struct VectorN {
+    components: Vec<f64>,
+}
+
+impl VectorN {
+    fn new(components: Vec<f64>) -> Self {
+        VectorN { components }
+    }
+
+    fn dimension(&self) -> usize {
+        self.components.len()
+    }
+
+    fn check_dimension(&self, other: &VectorN) -> Result<(), VectorError> {
+        if self.dimension() != other.dimension() {
+            return Err(VectorError::DimensionMismatch { expected: self.dimension(), found: other.dimension() });
+        }
+        Ok(())
+    }
+
+    fn zip_with(&self, other: &VectorN, f: impl Fn(f64, f64) -> f64) -> Result<VectorN, VectorError> {
+        self.check_dimension(other)?;
+        Ok(VectorN::new(self.components.iter().zip(&other.components).map(|(&a, &b)| f(a, b)).collect()))
+    }
+
+    fn add(&self, other: &VectorN) -> Result<VectorN, VectorError> {
+        self.zip_with(other, |a, b| a + b)
+    }
+
+    fn subtract(&self, other: &VectorN) -> Result<VectorN, VectorError> {
+        self.zip_with(other, |a, b| a - b)
+    }
+
+    fn dot_product(&self, other: &VectorN) -> Result<f64, VectorError> {
+        self.check_dimension(other)?;
+        Ok(self.components.iter().zip(&other.components).map(|(a, b)| a * b).sum())
+    }
+
+    // Binary cross product, which only exists in 3 and 7 dimensions. The 7D product follows
+    // the octonion multiplication table: e_i × e_j = e_k for each cyclic triple below.
+    fn cross_product(&self, other: &VectorN) -> Result<VectorN, VectorError> {
+        self.check_dimension(other)?;
+        let triples: &[(usize, usize, usize)] = match self.dimension() {
+            3 => &[(0, 1, 2)],
+            7 => &[(0, 1, 2), (0, 3, 4), (0, 6, 5), (1, 3, 5), (1, 4, 6), (2, 3, 6), (2, 5, 4)],
+            dimension => return Err(VectorError::UnsupportedDimension { operation: "cross product", dimension }),
+        };
+        let (a, b) = (&self.components, &other.components);
+        let mut result = vec![0.0; self.dimension()];
+        for &(i, j, k) in triples {
+            result[i] += a[j] * b[k] - a[k] * b[j];
+            result[j] += a[k] * b[i] - a[i] * b[k];
+            result[k] += a[i] * b[j] - a[j] * b[i];
+        }
+        Ok(VectorN::new(result))
+    }
+
+    // Generalized cross product of n - 1 vectors in n dimensions: the vector v with
+    // v · w = det(a1, …, an-1, w) for every w, found by cofactor expansion along w
+    fn generalized_cross_product(vectors: &[VectorN]) -> Result<VectorN, VectorError> {
+        // With no vectors at all this is the 1D case, whose result is (1)
+        let n = vectors.first().map_or(1, VectorN::dimension);
+        if n == 0 {
+            return Err(VectorError::UnsupportedDimension { operation: "generalized cross product", dimension: 0 });
+        }
+        for vector in vectors {
+            vectors[0].check_dimension(vector)?;
+        }
+        if vectors.len() != n - 1 {
+            return Err(VectorError::WrongVectorCount { expected: n - 1, found: vectors.len() });
+        }
+        let components = (0..n)
+            .map(|i| {
+                let minor: Vec<Vec<f64>> = vectors
+                    .iter()
+                    .map(|vector| vector.components.iter().enumerate().filter(|&(j, _)| j != i).map(|(_, &c)| c).collect())
+                    .collect();
+                let sign = if (n - 1 + i) % 2 == 1 { -1.0 } else { 1.0 };
+                sign * Matrix::new(n - 1, n - 1, minor).determinant().unwrap()
+            })
+            .collect();
+        Ok(VectorN::new(components))
+    }
+
+    fn magnitude(&self) -> f64 {
+        self.components.iter().map(|c| c * c).sum::<f64>().sqrt()
+    }
+
+    fn normalize(&self) -> Result<VectorN, VectorError> {
+        let magnitude = self.magnitude();
+        if magnitude == 0.0 {
+            return Err(VectorError::ZeroVector);
+        }
+        Ok(self.clone() / magnitude)
+    }
+
+    // Angle in radians, between 0 and π
+    fn angle_between(&self, other: &VectorN) -> Result<f64, VectorError> {
+        self.check_dimension(other)?;
+        let cosine = self.normalize()?.dot_product(&other.normalize()?)?;
+        Ok(cosine.clamp(-1.0, 1.0).acos())
+    }
+
+    fn angle_between_degrees(&self, other: &VectorN) -> Result<f64, VectorError> {
+        self.angle_between(other).map(f64::to_degrees)
+    }
+
+    fn project_onto(&self, other: &VectorN) -> Result<VectorN, VectorError> {
+        let length_squared = other.dot_product(other)?;
+        let dot = self.dot_product(other)?;
+        if length_squared == 0.0 {
+            return Err(VectorError::ZeroVector);
+        }
+        Ok(other.clone() * (dot / length_squared))
+    }
+
+    fn reject_from(&self, other: &VectorN) -> Result<VectorN, VectorError> {
+        self.subtract(&self.project_onto(other)?)
+    }
+
+    // Mirror image in the hyperplane through the origin with the given normal
+    fn reflect_across(&self, normal: &VectorN) -> Result<VectorN, VectorError> {
+        self.subtract(&(self.project_onto(normal)? * 2.0))
+    }
+
+    fn distance_to(&self, other: &VectorN) -> Result<f64, VectorError> {
+        Ok(self.subtract(other)?.magnitude())
+    }
+
+    // Same test as `Vector::is_parallel`, using |a|²|b|² - (a · b)² for |a × b|² so it works
+    // in any dimension
+    fn is_parallel(&self, other: &VectorN, tolerance: f64) -> Result<bool, VectorError> {
+        let dot = self.dot_product(other)?;
+        let product = self.magnitude() * other.magnitude();
+        Ok((product * product - dot * dot).max(0.0).sqrt() <= tolerance * product)
+    }
+
+    fn is_orthogonal(&self, other: &VectorN, tolerance: f64) -> Result<bool, VectorError> {
+        Ok(self.dot_product(other)?.abs() <= tolerance * self.magnitude() * other.magnitude())
+    }
+}
+
+impl fmt::Display for VectorN {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let components: Vec<String> = self.components.iter().map(|c| c.to_string()).collect();
+        write!(f, "({})", components.join(", "))
+    }
+}
+
+impl Neg for VectorN {
+    type Output = VectorN;
+
+    fn neg(self) -> VectorN {
+        self * -1.0
+    }
+}
+
+impl Mul<f64> for VectorN {
+    type Output = VectorN;
+
+    fn mul(self, scalar: f64) -> VectorN {
+        VectorN::new(self.components.iter().map(|c| c * scalar).collect())
+    }
+}
+
+impl Div<f64> for VectorN {
+    type Output = VectorN;
+
+    fn div(self, scalar: f64) -> VectorN {
+        VectorN::new(self.components.iter().map(|c| c / scalar).collect())
+    }
+}
+
+impl Index<usize> for VectorN {
+    type Output = f64;
+
+    fn index(&self, index: usize) -> &f64 {
+        &self.components[index]
+    }
+}
+
+impl From<Vector> for VectorN {
+    fn from(vector: Vector) -> Self {
+        VectorN::new(vec![vector.x, vector.y, vector.z])
+    }
+}
+
+impl TryFrom<&VectorN> for Vector {
+    type Error = VectorError;
+
+    fn try_from(vector: &VectorN) -> Result<Self, Self::Error> {
+        match vector.components[..] {
+            [x, y, z] => Ok(Vector::new(x, y, z)),
+            _ => Err(VectorError::DimensionMismatch { expected: 3, found: vector.dimension() }),
+        }
+    }
+}
+
+// Vector menu for vectors that are not both three-dimensional
+fn vector_n_operations(vector1: &VectorN, vector2: &VectorN) -> Result<(), Box<dyn Error>> {
+    println!("Vector: {}", vector1);
+    println!("Vector: {}", vector2);
+
+    let tolerance = 1e-9;
+    let show = |result: Result<String, VectorError>| result.unwrap_or_else(|e| e.to_string());
+    let mut results = vec![
+        ("Vector Addition", show(vector1.add(vector2).map(|v| v.to_string()))),
+        ("Vector Subtraction", show(vector1.subtract(vector2).map(|v| v.to_string()))),
+        ("Dot Product", show(vector1.dot_product(vector2).map(|dot| dot.to_string()))),
+        ("Cross Product", show(vector1.cross_product(vector2).map(|v| v.to_string()))),
+        ("Negation of the First Vector", (-vector1.clone()).to_string()),
+        ("Magnitude of the First Vector", vector1.magnitude().to_string()),
+        ("Magnitude of the Second Vector", vector2.magnitude().to_string()),
+        ("Unit Vector of the First Vector", show(vector1.normalize().map(|v| v.to_string()))),
+        ("Unit Vector of the Second Vector", show(vector2.normalize().map(|v| v.to_string()))),
+        ("Angle Between (radians)", show(vector1.angle_between(vector2).map(|angle| angle.to_string()))),
+        ("Angle Between (degrees)", show(vector1.angle_between_degrees(vector2).map(|angle| angle.to_string()))),
+        ("Projection of the First onto the Second", show(vector1.project_onto(vector2).map(|v| v.to_string()))),
+        ("Rejection of the First from the Second", show(vector1.reject_from(vector2).map(|v| v.to_string()))),
+        ("Reflection of the First across the Second as a Normal", show(vector1.reflect_across(vector2).map(|v| v.to_string()))),
+        ("Distance Between", show(vector1.distance_to(vector2).map(|distance| distance.to_string()))),
+        ("Parallel", show(vector1.is_parallel(vector2, tolerance).map(|parallel| parallel.to_string()))),
+        ("Orthogonal", show(vector1.is_orthogonal(vector2, tolerance).map(|orthogonal| orthogonal.to_string()))),
+    ];
+
+    // In n dimensions the cross product generalizes to n - 1 vectors
+    let n = vector1.dimension();
+    if n >= 2 {
+        println!("Calculate the generalized cross product of {} vectors in {} dimensions? (true or false)", n - 1, n);
+        if read_boolean_input() {
+            let mut vectors = vec![vector1.clone(), vector2.clone()];
+            vectors.truncate(n - 1);
+            for i in vectors.len() + 1..n {
+                println!("Enter vector {} of {}: ", i, n - 1);
+                vectors.push(read_vector_n());
+            }
+            results.push(("Generalized Cross Product", show(VectorN::generalized_cross_product(&vectors).map(|v| v.to_string()))));
+        }
+    }
+
+    for (name, result) in &results {
+        println!("\n{}:\n{}", name, result);
+    }
+
+    let file_path = "vector_operations.csv";
+    let mut writer = Writer::from_path(file_path)?;
+
+    writer.write_record(["Vector Operation", "Result"])?;
+    for (name, result) in &results {
+        writer.write_record([*name, result.as_str()])?;
+    }
+
+    writer.flush()?;
+    println!("Results have been written to {}", file_path);
+    Ok(())
+}
+
 #[derive(Debug)]
 struct Matrix{
     rows: usize,
@@ -88,6 +604,7 @@ struct Matrix{
     data: Vec<Vec<f64>>
 }
 
+#[allow(clippy::needless_range_loop)]
 impl Matrix{
     fn new(rows: usize, cols: usize, data: Vec<Vec<f64>>) -> Self {
         Matrix{ rows, cols, data }
@@ -136,6 +653,35 @@ impl Matrix{
         Matrix::new(self.cols, self.rows, result_data)
     }
 
+    // Determinant by Gaussian elimination with partial pivoting
+    fn determinant(&self) -> Result<f64, String> {
+        if self.rows != self.cols {
+            return Err(format!("A {}x{} matrix has no determinant", self.rows, self.cols));
+        }
+        let mut data = self.data.clone();
+        let mut determinant = 1.0;
+        for column in 0..self.cols {
+            let pivot = (column..self.rows)
+                .max_by(|&a, &b| data[a][column].abs().total_cmp(&data[b][column].abs()))
+                .unwrap();
+            if data[pivot][column] == 0.0 {
+                return Ok(0.0);
+            }
+            if pivot != column {
+                data.swap(pivot, column);
+                determinant = -determinant;
+            }
+            determinant *= data[column][column];
+            for row in column + 1..self.rows {
+                let factor = data[row][column] / data[column][column];
+                for k in column..self.cols {
+                    data[row][k] -= factor * data[column][k];
+                }
+            }
+        }
+        Ok(determinant)
+    }
+
     fn display_matrix(&self) {
         println!("Matrix:");
         for row in &self.data {
@@ -161,7 +707,6 @@ fn read_matrix() -> Matrix {
         let mut row_input = String::new();
         io::stdin().read_line(&mut row_input).expect("Failed to read line");
         let row_elements: Vec<f64> = row_input
-            .trim()
             .split_whitespace()
             .map(|s| s.parse().expect("Invalid input"))
             .collect();
@@ -219,176 +764,3677 @@ fn matrix_operations() -> Result<(), Box<dyn Error>>{
     let file_path = "matrix_operations.csv";
     let mut writer = Writer::from_path(file_path)?;
 
-    writer.write_record(&["Matrix Operation", "Result"])?;
-    writer.write_record(&["Matrix Addition", &format!("{:?}", result1)])?;
-    writer.write_record(&["Matrix Subtraction", &format!("{:?}", result2)])?;
-    writer.write_record(&["Matrix Multiplication", &format!("{:?}", result3)])?;
-    writer.write_record(&["Transposed Matrix 1", &format!("{:?}", result4)])?;
-    writer.write_record(&["Transposed Matrix 2", &format!("{:?}", result5)])?;
+    writer.write_record(["Matrix Operation", "Result"])?;
+    writer.write_record(["Matrix Addition", &format!("{:?}", result1)])?;
+    writer.write_record(["Matrix Subtraction", &format!("{:?}", result2)])?;
+    writer.write_record(["Matrix Multiplication", &format!("{:?}", result3)])?;
+    writer.write_record(["Transposed Matrix 1", &format!("{:?}", result4)])?;
+    writer.write_record(["Transposed Matrix 2", &format!("{:?}", result5)])?;
 
     println!("Results saved to {}", file_path);
     Ok(())
 }
 
-#[allow(unused_must_use)]
-#[derive(Debug)]
-#[derive(Clone)]
-struct Set {
-    elements: Vec<i32>,
+// f64 wrapper with a total order so floating-point values can be set elements
+#[derive(Clone, Copy)]
+struct OrderedF64(f64);
+
+impl PartialEq for OrderedF64 {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OrderedF64 {}
+
+impl PartialOrd for OrderedF64 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrderedF64 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl Hash for OrderedF64 {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state);
+    }
+}
+
+impl fmt::Debug for OrderedF64 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+impl From<OrderedF64> for f64 {
+    fn from(value: OrderedF64) -> f64 {
+        value.0
+    }
+}
+
+impl fmt::Display for OrderedF64 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for OrderedF64 {
+    type Err = ParseFloatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(OrderedF64)
+    }
+}
+
+#[allow(unused_must_use)]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Set<T: Ord + Hash + Clone = i32> {
+    elements: BTreeSet<T>,
+}
+
+#[allow(unused_must_use)]
+impl<T: Ord + Hash + Clone> Set<T> {
+    fn new() -> Self {
+        Set { elements: BTreeSet::new() }
+    }
+
+    fn add(&mut self, element: T) {
+        self.elements.insert(element);
+    }
+
+    fn remove(&mut self, element: T) {
+        self.elements.remove(&element);
+    }
+
+    fn empty_set(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    // Elements in ascending order
+    fn to_vec(&self) -> Vec<T> {
+        self.elements.iter().cloned().collect()
+    }
+
+    fn equal_sets(&self, other: &Set<T>) -> bool {
+        self.elements == other.elements
+    }
+
+    fn unequal_sets(&self, other: &Set<T>) -> bool {
+        !self.equal_sets(other)
+    }
+
+    // Number of elements in the set
+    fn cardinality(&self) -> usize {
+        self.elements.len()
+    }
+
+    // Equivalent sets have the same cardinality, not necessarily the same elements
+    fn equivalent_sets<U: Ord + Hash + Clone>(&self, other: &Set<U>) -> bool {
+        self.cardinality() == other.cardinality()
+    }
+
+    fn overlapping_sets(&self, other: &Set<T>) -> bool {
+        !self.elements.is_disjoint(&other.elements)
+    }
+
+    fn disjoint_sets(&self, other: &Set<T>) -> bool {
+        !self.overlapping_sets(other)
+    }
+
+    // Check if this set is a subset of another set
+    fn is_subset(&self, other: &Set<T>) -> bool {
+        self.elements.is_subset(&other.elements)
+    }
+
+    // Check if this set is a superset of another set
+    fn is_superset(&self, other: &Set<T>) -> bool {
+        other.is_subset(self)
+    }
+
+    // Subset of another set that is not equal to it
+    fn is_proper_subset(&self, other: &Set<T>) -> bool {
+        self.cardinality() < other.cardinality() && self.is_subset(other)
+    }
+
+    // Superset of another set that is not equal to it
+    fn is_proper_superset(&self, other: &Set<T>) -> bool {
+        other.is_proper_subset(self)
+    }
+
+    // One-to-one correspondence pairing the elements of both sets in ascending order,
+    // which exists exactly when the sets are equivalent
+    fn bijection_to<U: Ord + Hash + Clone>(&self, other: &Set<U>) -> Option<Vec<(T, U)>> {
+        if !self.equivalent_sets(other) {
+            return None;
+        }
+        Some(self.elements.iter().cloned().zip(other.elements.iter().cloned()).collect())
+    }
+
+    // Lazily walk the power set without materializing it
+    fn power_set_iter(&self, order: SubsetOrder) -> PowerSetIter<T> {
+        PowerSetIter::new(self.to_vec(), order)
+    }
+
+    // Lazily walk the subsets with exactly `k` elements, i.e. the k-combinations
+    fn subsets_of_size(&self, k: usize) -> SubsetsOfSize<T> {
+        SubsetsOfSize::new(self.to_vec(), k)
+    }
+
+    // Lazily walk the ordered arrangements of `k` distinct elements
+    fn permutations(&self, k: usize) -> Permutations<T> {
+        Permutations::new(self.to_vec(), k)
+    }
+
+    // Lazily walk the ways to choose `k` elements when each may be chosen more than once
+    fn combinations_with_repetition(&self, k: usize) -> CombinationsWithRepetition<T> {
+        CombinationsWithRepetition::new(self.to_vec(), k)
+    }
+
+    // Lazily walk the orderings of all elements in which no element keeps its sorted position
+    fn derangements(&self) -> Derangements<T> {
+        Derangements::new(self.to_vec())
+    }
+
+    // Number of subsets, 2^n, which does not fit in a u64 once n reaches 64
+    fn power_set_len(&self) -> BigUint {
+        BigUint::from(1u32) << self.elements.len()
+    }
+
+    // Lazily walk every partition of the set into non-empty blocks
+    fn partitions(&self) -> PartitionIter<T> {
+        PartitionIter::new(self.to_vec())
+    }
+
+    // Elements that are in this set, the other set, or both
+    fn union(&self, other: &Set<T>) -> Set<T> {
+        Set { elements: self.elements.union(&other.elements).cloned().collect() }
+    }
+
+    // Elements that are in both sets
+    fn intersection(&self, other: &Set<T>) -> Set<T> {
+        Set { elements: self.elements.intersection(&other.elements).cloned().collect() }
+    }
+
+    // Elements of this set that are not in the other set
+    fn difference(&self, other: &Set<T>) -> Set<T> {
+        Set { elements: self.elements.difference(&other.elements).cloned().collect() }
+    }
+
+    // Elements that are in exactly one of the two sets
+    fn symmetric_difference(&self, other: &Set<T>) -> Set<T> {
+        Set { elements: self.elements.symmetric_difference(&other.elements).cloned().collect() }
+    }
+
+    // Elements of the universe that are not in this set
+    fn complement(&self, universe: &Set<T>) -> Set<T> {
+        universe.difference(self)
+    }
+
+    // Every pair (a, b) with a in this set and b in the other set
+    fn cartesian_product<U: Ord + Hash + Clone>(&self, other: &Set<U>) -> Set<(T, U)> {
+        self.elements
+            .iter()
+            .flat_map(|a| other.elements.iter().map(move |b| (a.clone(), b.clone())))
+            .collect()
+    }
+}
+
+// Prints like the element set itself, e.g. `{1, 2}`, so nested sets stay readable
+impl<T: Ord + Hash + Clone + fmt::Debug> fmt::Debug for Set<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(&self.elements).finish()
+    }
+}
+
+// Brace notation with the elements' own formatting, e.g. `{1, 2}` or `{{1}, {2, 3}}`
+impl<T: Ord + Hash + Clone + fmt::Display> fmt::Display for Set<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let elements: Vec<String> = self.elements.iter().map(|element| element.to_string()).collect();
+        write!(f, "{{{}}}", elements.join(", "))
+    }
+}
+
+// Largest number of elements a single range such as `{1..10}` may expand to
+const MAX_RANGE_LEN: i64 = 1_000_000;
+
+// Parses brace notation such as `{1, 2, 3}`, nested sets like `{{1}, {2, 3}}`, inclusive
+// integer ranges like `{1..10}`, and `{}` or `∅` for the empty set. Elements whose text
+// contains `,`, `{` or `}` cannot be written this way, so such sets do not round-trip
+impl<T: Ord + Hash + Clone + FromStr> FromStr for Set<T> {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let input = input.trim();
+        if input == "∅" {
+            return Ok(Set::new());
+        }
+        let inner = input
+            .strip_prefix('{')
+            .and_then(|rest| rest.strip_suffix('}'))
+            .ok_or_else(|| format!("Expected a set in braces, e.g. {{1, 2, 3}}, found `{}`", input))?;
+        let mut set = Set::new();
+        if inner.trim().is_empty() {
+            return Ok(set);
+        }
+
+        // Split on the commas that are not inside a nested set
+        let mut items = Vec::new();
+        let mut depth = 0usize;
+        let mut start = 0;
+        for (i, c) in inner.char_indices() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth = depth.checked_sub(1).ok_or_else(|| format!("Unbalanced braces in `{}`", input))?,
+                ',' if depth == 0 => {
+                    items.push(&inner[start..i]);
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        if depth != 0 {
+            return Err(format!("Unbalanced braces in `{}`", input));
+        }
+        items.push(&inner[start..]);
+
+        for item in items.into_iter().map(str::trim) {
+            if item.is_empty() {
+                return Err(format!("Missing element in `{}`", input));
+            }
+            let parse = |text: &str| text.parse::<T>().map_err(|_| format!("Invalid element `{}`", text));
+            let range = item
+                .split_once("..")
+                .and_then(|(first, last)| Some((first.trim().parse::<i64>().ok()?, last.trim().parse::<i64>().ok()?)));
+            match range {
+                Some((first, last)) => {
+                    parse(&first.to_string())?;
+                    parse(&last.to_string())?;
+                    if last.saturating_sub(first) >= MAX_RANGE_LEN {
+                        return Err(format!("The range `{}` has more than {} elements", item, MAX_RANGE_LEN));
+                    }
+                    for n in first..=last {
+                        set.add(parse(&n.to_string())?);
+                    }
+                }
+                _ => set.add(parse(item)?),
+            }
+        }
+        Ok(set)
+    }
+}
+
+impl<T: Ord + Hash + Clone> FromIterator<T> for Set<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Set::new();
+        for element in iter {
+            set.add(element);
+        }
+        set
+    }
+}
+
+// Order in which `PowerSetIter` produces subsets
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SubsetOrder {
+    // Subset i contains element j when bit j of i is set
+    Binary,
+    // Consecutive subsets differ by exactly one element
+    Gray,
+}
+
+// Streams every subset of a set; the counter is a bit vector rather than an integer,
+// so it works for sets of any size
+struct PowerSetIter<T> {
+    elements: Vec<T>,
+    order: SubsetOrder,
+    counter: Vec<bool>,
+    current: Vec<bool>,
+    done: bool,
+}
+
+impl<T: Clone> PowerSetIter<T> {
+    fn new(elements: Vec<T>, order: SubsetOrder) -> Self {
+        let size = elements.len();
+        PowerSetIter { elements, order, counter: vec![false; size], current: vec![false; size], done: false }
+    }
+}
+
+impl<T: Clone> Iterator for PowerSetIter<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.done {
+            return None;
+        }
+
+        let subset = self.elements
+            .iter()
+            .zip(&self.current)
+            .filter(|(_, &member)| member)
+            .map(|(element, _)| element.clone())
+            .collect();
+
+        // Increment the binary counter; the bit it stops at is also the bit that
+        // changes between consecutive Gray codes
+        match self.counter.iter().position(|&bit| !bit) {
+            Some(j) => {
+                self.counter[..j].iter_mut().for_each(|bit| *bit = false);
+                self.counter[j] = true;
+                match self.order {
+                    SubsetOrder::Binary => self.current.clone_from(&self.counter),
+                    SubsetOrder::Gray => self.current[j] = !self.current[j],
+                }
+            }
+            None => self.done = true,
+        }
+
+        Some(subset)
+    }
+}
+
+// Streams the k-element subsets of a set in lexicographic order of positions
+struct SubsetsOfSize<T> {
+    elements: Vec<T>,
+    indices: Vec<usize>,
+    done: bool,
+}
+
+impl<T: Clone> SubsetsOfSize<T> {
+    fn new(elements: Vec<T>, k: usize) -> Self {
+        let done = k > elements.len();
+        SubsetsOfSize { elements, indices: (0..k).collect(), done }
+    }
+}
+
+impl<T: Clone> Iterator for SubsetsOfSize<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.done {
+            return None;
+        }
+
+        let subset = self.indices.iter().map(|&i| self.elements[i].clone()).collect();
+
+        // Find the rightmost index that can still move right, then reset the ones after it
+        let n = self.elements.len();
+        let k = self.indices.len();
+        match (0..k).rev().find(|&i| self.indices[i] < n - k + i) {
+            Some(i) => {
+                self.indices[i] += 1;
+                for j in i + 1..k {
+                    self.indices[j] = self.indices[j - 1] + 1;
+                }
+            }
+            None => self.done = true,
+        }
+
+        Some(subset)
+    }
+}
+
+// Streams the partitions of a set using restricted growth strings: element i goes in
+// block `blocks[i]`, and each element opens at most one block past the ones used before it
+struct PartitionIter<T> {
+    elements: Vec<T>,
+    blocks: Vec<usize>,
+    done: bool,
+}
+
+impl<T: Ord + Hash + Clone> PartitionIter<T> {
+    fn new(elements: Vec<T>) -> Self {
+        let blocks = vec![0; elements.len()];
+        PartitionIter { elements, blocks, done: false }
+    }
+}
+
+impl<T: Ord + Hash + Clone> Iterator for PartitionIter<T> {
+    type Item = Vec<Set<T>>;
+
+    fn next(&mut self) -> Option<Vec<Set<T>>> {
+        if self.done {
+            return None;
+        }
+
+        let block_count = self.blocks.iter().max().map_or(0, |&max| max + 1);
+        let mut partition = vec![Set::new(); block_count];
+        for (element, &block) in self.elements.iter().zip(&self.blocks) {
+            partition[block].add(element.clone());
+        }
+
+        // Increment the rightmost position that may still grow, then reset the ones after it
+        let prefix_max: Vec<usize> = self
+            .blocks
+            .iter()
+            .scan(0, |max, &block| {
+                *max = (*max).max(block);
+                Some(*max)
+            })
+            .collect();
+        match (1..self.blocks.len()).rev().find(|&i| self.blocks[i] <= prefix_max[i - 1]) {
+            Some(i) => {
+                self.blocks[i] += 1;
+                self.blocks[i + 1..].iter_mut().for_each(|block| *block = 0);
+            }
+            None => self.done = true,
+        }
+
+        Some(partition)
+    }
+}
+
+// Stirling number of the second kind: the number of ways to partition n elements into k blocks
+fn stirling_second_kind(n: usize, k: usize) -> BigUint {
+    stirling_row(n).get(k).cloned().unwrap_or_default()
+}
+
+// S(n, 0..=n), built from S(n, k) = k * S(n - 1, k) + S(n - 1, k - 1)
+fn stirling_row(n: usize) -> Vec<BigUint> {
+    let mut row = vec![BigUint::from(1u32)];
+    for m in 1..=n {
+        let mut next = vec![BigUint::default(); m + 1];
+        for k in 1..=m {
+            let stay = row.get(k).map_or_else(BigUint::default, |s| s * BigUint::from(k));
+            next[k] = stay + &row[k - 1];
+        }
+        row = next;
+    }
+    row
+}
+
+// Bell number: the number of partitions of n elements
+fn bell_number(n: usize) -> BigUint {
+    stirling_row(n).iter().sum()
+}
+
+// Elements of two or three sets grouped by the Venn region they lie in
+struct VennDiagram<T> {
+    labels: Vec<String>,
+    // Which sets each region is inside of, and the elements in that region
+    regions: Vec<(Vec<bool>, Vec<T>)>,
+}
+
+// A set drawn as a rectangle of grid cells: first and last column, first and last row,
+// the column its label is written in, and whether the label goes on the top border
+type VennRectangle = (usize, usize, usize, usize, usize, bool);
+
+// A region's set membership, its grid cell (column, row) and its position in the SVG
+type VennCell = (Vec<bool>, (usize, usize), (f64, f64));
+
+// Grid layout of the ASCII diagram, plus the grid cell and SVG position of each region
+fn venn_layout(set_count: usize) -> (Vec<VennRectangle>, Vec<VennCell>) {
+    if set_count == 2 {
+        (
+            vec![(0, 1, 0, 1, 0, true), (1, 2, 1, 2, 2, false)],
+            vec![
+                (vec![true, false], (0, 0), (90.0, 155.0)),
+                (vec![true, true], (1, 1), (200.0, 155.0)),
+                (vec![false, true], (2, 2), (310.0, 155.0)),
+            ],
+        )
+    } else {
+        (
+            vec![(0, 2, 0, 1, 0, true), (2, 4, 0, 1, 4, true), (1, 3, 1, 2, 2, false)],
+            vec![
+                (vec![true, false, false], (0, 0), (105.0, 115.0)),
+                (vec![true, true, false], (2, 0), (200.0, 100.0)),
+                (vec![false, true, false], (4, 0), (295.0, 115.0)),
+                (vec![true, false, true], (1, 1), (140.0, 205.0)),
+                (vec![true, true, true], (2, 1), (200.0, 170.0)),
+                (vec![false, true, true], (3, 1), (260.0, 205.0)),
+                (vec![false, false, true], (2, 2), (200.0, 290.0)),
+            ],
+        )
+    }
+}
+
+impl<T: Ord + Hash + Clone + fmt::Debug> VennDiagram<T> {
+    fn new(labels: &[&str], sets: &[&Set<T>]) -> Result<VennDiagram<T>, String> {
+        if labels.len() != sets.len() || !(2..=3).contains(&sets.len()) {
+            return Err("A Venn diagram needs two or three labelled sets".to_string());
+        }
+
+        let (_, cells) = venn_layout(sets.len());
+        let regions = cells
+            .into_iter()
+            .map(|(membership, _, _)| {
+                let elements = sets
+                    .iter()
+                    .fold(Set::new(), |all, set| all.union(set))
+                    .elements
+                    .into_iter()
+                    .filter(|e| sets.iter().zip(&membership).all(|(set, &inside)| set.elements.contains(e) == inside))
+                    .collect();
+                (membership, elements)
+            })
+            .collect();
+
+        Ok(VennDiagram { labels: labels.iter().map(|label| label.to_string()).collect(), regions })
+    }
+
+    // Region name such as `A ∩ B' ∩ C`
+    fn region_name(&self, membership: &[bool]) -> String {
+        self.labels
+            .iter()
+            .zip(membership)
+            .map(|(label, &inside)| if inside { label.clone() } else { format!("{}'", label) })
+            .collect::<Vec<String>>()
+            .join(" ∩ ")
+    }
+
+    fn region_counts(&self) -> Vec<(String, usize)> {
+        self.regions.iter().map(|(membership, elements)| (self.region_name(membership), elements.len())).collect()
+    }
+
+    fn region_text(elements: &[T]) -> Vec<String> {
+        elements.iter().map(|e| format!("{:?}", e)).collect()
+    }
+
+    // Draws each set as a rectangle on a character grid, with the elements of each region
+    // written in the grid cell where exactly the right rectangles overlap
+    fn to_ascii(&self) -> String {
+        let (rectangles, cells) = venn_layout(self.labels.len());
+        let columns = rectangles.iter().map(|r| r.1).max().unwrap_or(0) + 1;
+        let rows = rectangles.iter().map(|r| r.3).max().unwrap_or(0) + 1;
+
+        let texts: Vec<String> = self.regions.iter().map(|(_, elements)| Self::region_text(elements).join(" ")).collect();
+        let mut widths = vec![3; columns];
+        for ((_, (column, _), _), text) in cells.iter().zip(&texts) {
+            widths[*column] = widths[*column].max(text.chars().count() + 2);
+        }
+        for (label, rectangle) in self.labels.iter().zip(&rectangles) {
+            widths[rectangle.4] = widths[rectangle.4].max(label.chars().count() + 3);
+        }
+
+        // Grid lines sit between the cells, each cell is one text line high
+        let mut xs = vec![0];
+        for width in &widths {
+            xs.push(xs[xs.len() - 1] + width + 1);
+        }
+        let ys: Vec<usize> = (0..=rows).map(|row| row * 2).collect();
+
+        let mut canvas = vec![vec![' '; xs[columns] + 1]; ys[rows] + 1];
+        let draw = |canvas: &mut Vec<Vec<char>>, x: usize, y: usize, line: char| {
+            let current = canvas[y][x];
+            canvas[y][x] = if current == ' ' || current == line { line } else { '+' };
+        };
+        for &(first_column, last_column, first_row, last_row, _, _) in &rectangles {
+            let (left, right) = (xs[first_column], xs[last_column + 1]);
+            let (top, bottom) = (ys[first_row], ys[last_row + 1]);
+            for x in left..=right {
+                draw(&mut canvas, x, top, '-');
+                draw(&mut canvas, x, bottom, '-');
+            }
+            for y in top..=bottom {
+                draw(&mut canvas, left, y, '|');
+                draw(&mut canvas, right, y, '|');
+            }
+            for (x, y) in [(left, top), (right, top), (left, bottom), (right, bottom)] {
+                canvas[y][x] = '+';
+            }
+        }
+
+        for (label, &(_, _, first_row, last_row, label_column, label_on_top)) in self.labels.iter().zip(&rectangles) {
+            let y = if label_on_top { ys[first_row] } else { ys[last_row + 1] };
+            for (i, c) in label.chars().enumerate() {
+                canvas[y][xs[label_column] + 2 + i] = c;
+            }
+        }
+
+        for ((_, (column, row), _), text) in cells.iter().zip(&texts) {
+            for (i, c) in text.chars().enumerate() {
+                canvas[ys[*row] + 1][xs[*column] + 2 + i] = c;
+            }
+        }
+
+        canvas
+            .iter()
+            .map(|line| line.iter().collect::<String>().trim_end().to_string())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn to_svg(&self) -> String {
+        let (_, cells) = venn_layout(self.labels.len());
+        let (circles, height): (Vec<(f64, f64)>, usize) = if self.labels.len() == 2 {
+            (vec![(140.0, 150.0), (260.0, 150.0)], 300)
+        } else {
+            (vec![(150.0, 140.0), (250.0, 140.0), (200.0, 226.0)], 360)
+        };
+        let colors = ["#e41a1c", "#377eb8", "#4daf4a"];
+        let escape = |text: &str| text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"400\" height=\"{}\" viewBox=\"0 0 400 {}\">\n",
+            height, height
+        );
+        for (((cx, cy), color), label) in circles.iter().zip(colors).zip(&self.labels) {
+            svg.push_str(&format!(
+                "  <circle cx=\"{}\" cy=\"{}\" r=\"100\" fill=\"{}\" fill-opacity=\"0.2\" stroke=\"{}\" />\n",
+                cx, cy, color, color
+            ));
+            let label_y = if *cy > 200.0 { cy + 118.0 } else { cy - 108.0 };
+            svg.push_str(&format!(
+                "  <text x=\"{}\" y=\"{}\" text-anchor=\"middle\" font-weight=\"bold\">{}</text>\n",
+                cx, label_y, escape(label)
+            ));
+        }
+
+        // Long regions wrap onto several lines of four elements
+        for ((_, _, (x, y)), (_, elements)) in cells.iter().zip(&self.regions) {
+            let text = Self::region_text(elements);
+            let lines: Vec<String> = text.chunks(4).map(|chunk| escape(&chunk.join(" "))).collect();
+            let top = y - 7.0 * (lines.len().max(1) - 1) as f64;
+            svg.push_str(&format!("  <text x=\"{}\" y=\"{}\" text-anchor=\"middle\" font-size=\"12\">", x, top));
+            for (i, line) in lines.iter().enumerate() {
+                let dy = if i == 0 { 0 } else { 14 };
+                svg.push_str(&format!("<tspan x=\"{}\" dy=\"{}\">{}</tspan>", x, dy, line));
+            }
+            svg.push_str("</text>\n");
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+// Parses either brace notation, e.g. `{1, 2, 3}` or `{1..10}`, or space-separated elements
+fn parse_set<T>(input: &str) -> Result<Set<T>, String>
+where
+    T: Ord + Hash + Clone + FromStr,
+{
+    let input = input.trim();
+    if input.starts_with('{') || input == "∅" {
+        return input.parse();
+    }
+    input
+        .split_whitespace()
+        .map(|s| s.parse().map_err(|_| format!("Invalid element `{}`", s)))
+        .collect()
+}
+
+// Reads one set from a line; works for any element type that can be parsed, such as integers,
+// strings or `OrderedF64`. Asks again until the line can be parsed.
+fn read_set<T>() -> Set<T>
+where
+    T: Ord + Hash + Clone + FromStr,
+    T::Err: fmt::Debug,
+{
+    loop {
+        println!("Enter elements of the set (space-separated, or in braces such as {{1, 2, 3}} or {{1..10}}):");
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).expect("Failed to read line");
+        match parse_set(&input) {
+            Ok(set) => return set,
+            Err(e) => println!("{}. Please try again.", e),
+        }
+    }
+}
+
+fn read_element<T>() -> T
+where
+    T: FromStr,
+    T::Err: fmt::Debug,
+{
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).expect("Failed to read line");
+    input.trim().parse().expect("Invalid input")
+}
+
+// Element types the set menu can read
+enum ElementType {
+    Integer,
+    Text,
+    Float,
+}
+
+fn read_element_type() -> Result<ElementType, Box<dyn Error>> {
+    println!("Select the type of set elements:");
+    println!("1. Integers");
+    println!("2. Strings");
+    println!("3. Floating-point numbers");
+
+    let mut choice = String::new();
+    io::stdin().read_line(&mut choice)?;
+
+    match choice.trim() {
+        "1" => Ok(ElementType::Integer),
+        "2" => Ok(ElementType::Text),
+        "3" => Ok(ElementType::Float),
+        _ => Err("Invalid element type".into()),
+    }
+}
+
+fn set_operations() -> Result<(), Box<dyn Error>>{
+    println!("Select a set operation:");
+    println!("1. Compare two sets");
+    println!("2. Evaluate set expressions");
+    println!("3. Prove set identities");
+    println!("4. Check a binary relation");
+    println!("5. List the partitions of a set");
+    println!("6. Check a function between sets");
+    println!("7. Analyse a partial order");
+    println!("8. Calculate fuzzy sets");
+    println!("9. Calculate real interval sets");
+    println!("10. Count a union by inclusion-exclusion");
+    println!("11. Form Cartesian products");
+    println!("12. Merge elements with union-find");
+    println!("13. Calculate finite probabilities");
+
+    let mut choice = String::new();
+    io::stdin().read_line(&mut choice)?;
+
+    match choice.trim() {
+        "1" => match read_element_type()? {
+            ElementType::Integer => typed_set_operations::<i32>(),
+            ElementType::Text => typed_set_operations::<String>(),
+            ElementType::Float => typed_set_operations::<OrderedF64>(),
+        },
+        "2" => match read_element_type()? {
+            ElementType::Integer => set_expression_operations::<i32>(),
+            ElementType::Text => set_expression_operations::<String>(),
+            ElementType::Float => set_expression_operations::<OrderedF64>(),
+        },
+        "3" => set_identity_operations(),
+        "4" => match read_element_type()? {
+            ElementType::Integer => relation_operations::<i32>(),
+            ElementType::Text => relation_operations::<String>(),
+            ElementType::Float => relation_operations::<OrderedF64>(),
+        },
+        "5" => match read_element_type()? {
+            ElementType::Integer => partition_operations::<i32>(),
+            ElementType::Text => partition_operations::<String>(),
+            ElementType::Float => partition_operations::<OrderedF64>(),
+        },
+        "6" => match read_element_type()? {
+            ElementType::Integer => mapping_operations::<i32>(),
+            ElementType::Text => mapping_operations::<String>(),
+            ElementType::Float => mapping_operations::<OrderedF64>(),
+        },
+        "7" => poset_operations(),
+        "8" => match read_element_type()? {
+            ElementType::Integer => fuzzy_set_operations::<i32>(),
+            ElementType::Float => fuzzy_set_operations::<OrderedF64>(),
+            ElementType::Text => Err("Fuzzy sets need numeric elements".into()),
+        },
+        "9" => interval_set_operations(),
+        "10" => inclusion_exclusion_operations(),
+        "11" => match read_element_type()? {
+            ElementType::Integer => product_operations::<i32>(),
+            ElementType::Text => product_operations::<String>(),
+            ElementType::Float => product_operations::<OrderedF64>(),
+        },
+        "12" => match read_element_type()? {
+            ElementType::Integer => disjoint_sets_operations::<i32>(),
+            ElementType::Text => disjoint_sets_operations::<String>(),
+            ElementType::Float => disjoint_sets_operations::<OrderedF64>(),
+        },
+        "13" => match read_element_type()? {
+            ElementType::Integer => probability_operations::<i32>(),
+            ElementType::Text => probability_operations::<String>(),
+            ElementType::Float => probability_operations::<OrderedF64>(),
+        },
+        _ => Err("Invalid choice".into()),
+    }
+}
+
+// Subsets, partitions and arrangements grow exponentially or factorially, so larger families
+// are counted but not listed
+const MAX_LISTED_ROWS: u32 = 1_000_000;
+
+// Writes one row per item, streaming them, unless `count` exceeds MAX_LISTED_ROWS
+fn write_listing<I>(writer: &mut Writer<fs::File>, label: &str, count: &BigUint, items: I) -> Result<(), Box<dyn Error>>
+where
+    I: IntoIterator,
+    I::Item: fmt::Debug,
+{
+    if *count > BigUint::from(MAX_LISTED_ROWS) {
+        println!("{}: {} is too many to list, so only the count is saved", label, count);
+        return Ok(());
+    }
+    for item in items {
+        writer.write_record([label, &format!("{:?}", item)])?;
+    }
+    Ok(())
+}
+
+#[allow(unused_must_use)]
+fn typed_set_operations<T>() -> Result<(), Box<dyn Error>>
+where
+    T: Ord + Hash + Clone + fmt::Debug + FromStr,
+    T::Err: fmt::Debug,
+{
+    let set1: Set<T> = read_set();
+    let set2: Set<T> = read_set();
+    println!("Enter the universe set (used for complements):");
+    let universe = read_set();
+    println!("Add a third set to the Venn diagram? (true or false)");
+    let set3: Option<Set<T>> = if read_boolean_input() { Some(read_set()) } else { None };
+
+    println!("\n--------------Checking Sets----------------\n");
+
+    let venn = match &set3 {
+        Some(set3) => VennDiagram::new(&["A", "B", "C"], &[&set1, &set2, set3])?,
+        None => VennDiagram::new(&["A", "B"], &[&set1, &set2])?,
+    };
+    println!("\nVenn diagram (A is Set 1, B is Set 2{}): \n{}", if set3.is_some() { ", C is Set 3" } else { "" }, venn.to_ascii());
+
+    println!("\nIs Set 1 empty? \n{}", set1.empty_set());
+    println!("\nIs Set 2 empty? \n{}", set2.empty_set());
+
+    println!("\nIs it equal Sets? \n{}", set1.equal_sets(&set2));
+    println!("\nIs it unequal Sets? \n{}", set1.unequal_sets(&set2));
+    println!("\nIs it equivalent Sets? \n{}", set1.equivalent_sets(&set2));
+    println!("\nIs it overlapping Sets? \n{}", set1.overlapping_sets(&set2));
+    println!("\nIs it disjoint Sets? \n{}", set1.disjoint_sets(&set2));
+
+    println!("\nSet 1 is Subset of Set 2? \n{}", set1.is_subset(&set2));
+    println!("\nSet 1 is Superset of Set 2? \n{}", set1.is_superset(&set2));
+    println!("\nSet 1 is Proper Subset of Set 2? \n{}", set1.is_proper_subset(&set2));
+    println!("\nSet 1 is Proper Superset of Set 2? \n{}", set1.is_proper_superset(&set2));
+
+    println!("\nCardinality of Set 1: \n{}", set1.cardinality());
+    println!("\nCardinality of Set 2: \n{}", set2.cardinality());
+
+    let bijection = set1.bijection_to(&set2);
+    match &bijection {
+        Some(pairs) => println!("\nBijection from Set 1 to Set 2: \n{:?}", pairs),
+        None => println!("\nBijection from Set 1 to Set 2: \nNone (the sets have different cardinalities)"),
+    }
+
+    println!("\nNumber of subsets of Set 1: \n{}", set1.power_set_len());
+    println!("\nNumber of subsets of Set 2: \n{}", set2.power_set_len());
+
+    println!("List power set subsets in Gray-code order? (true or false)");
+    let order = if read_boolean_input() { SubsetOrder::Gray } else { SubsetOrder::Binary };
+
+    println!("Enter a subset size k to list the k-element subsets of Set 1:");
+    let subset_size: usize = read_element();
+
+    let union = set1.union(&set2);
+    let intersection = set1.intersection(&set2);
+    let difference = set1.difference(&set2);
+    let symmetric_difference = set1.symmetric_difference(&set2);
+    let complement1 = set1.complement(&universe);
+    let complement2 = set2.complement(&universe);
+
+    println!("\nUnion of Set 1 and Set 2: \n{:?}", union.elements);
+    println!("\nIntersection of Set 1 and Set 2: \n{:?}", intersection.elements);
+    println!("\nDifference of Set 1 and Set 2: \n{:?}", difference.elements);
+    println!("\nSymmetric Difference of Set 1 and Set 2: \n{:?}", symmetric_difference.elements);
+    println!("\nComplement of Set 1: \n{:?}", complement1.elements);
+    println!("\nComplement of Set 2: \n{:?}", complement2.elements);
+
+    println!("-------------Adding and Removing Sets--------------");
+
+    println!("Enter new set to add or remove the element:");
+    let mut set: Set<T> = read_set();
+
+    // Add elements to the set
+    println!("Enter element to add:");
+    let add_element: T = read_element();
+    set.add(add_element.clone());
+    println!("Set after adding {:?}: {:?}", add_element, set.elements);
+
+    // Remove an element from the set
+    println!("Enter element to remove:");
+    let remove_element: T = read_element();
+    set.remove(remove_element.clone());
+    println!("Set after removing {:?}: {:?}", remove_element, set.elements);
+
+    // Open the CSV file for writing
+    let file_path = "set_operations.csv";
+    let mut writer = Writer::from_path(file_path)?;
+
+    // Write set operation results to the CSV file
+    writer.write_record(["Is Empty Set", &set1.empty_set().to_string()]);
+    writer.write_record(["Is Equal Sets", &set1.equal_sets(&set2).to_string()]);
+    writer.write_record(["Is Unequal Sets", &set1.unequal_sets(&set2).to_string()]);
+    writer.write_record(["Is Equivalent Sets", &set1.equivalent_sets(&set2).to_string()]);
+    writer.write_record(["Is Overlapping Sets", &set1.overlapping_sets(&set2).to_string()]);
+    writer.write_record(["Is Disjoint Sets", &set1.disjoint_sets(&set2).to_string()]);
+    writer.write_record(["Is Subset", &set1.is_subset(&set2).to_string()]);
+    writer.write_record(["Is Superset", &set1.is_superset(&set2).to_string()]);
+    writer.write_record(["Is Proper Subset", &set1.is_proper_subset(&set2).to_string()]);
+    writer.write_record(["Is Proper Superset", &set1.is_proper_superset(&set2).to_string()]);
+    writer.write_record(["Cardinality of Set 1", &set1.cardinality().to_string()]);
+    writer.write_record(["Cardinality of Set 2", &set2.cardinality().to_string()]);
+    writer.write_record(["Bijection", &format!("{:?}", bijection)]);
+    for (region, count) in venn.region_counts() {
+        writer.write_record([&format!("Venn Region {}", region), &count.to_string()])?;
+    }
+    writer.write_record(["Union", &format!("{:?}", union.elements)]);
+    writer.write_record(["Intersection", &format!("{:?}", intersection.elements)]);
+    writer.write_record(["Difference", &format!("{:?}", difference.elements)]);
+    writer.write_record(["Symmetric Difference", &format!("{:?}", symmetric_difference.elements)]);
+    writer.write_record(["Complement of Set 1", &format!("{:?}", complement1.elements)]);
+    writer.write_record(["Complement of Set 2", &format!("{:?}", complement2.elements)]);
+    writer.write_record(["Power Set Size of Set 1", &set1.power_set_len().to_string()]);
+    writer.write_record(["Power Set Size of Set 2", &set2.power_set_len().to_string()]);
+
+    write_listing(&mut writer, "Power Set of Set 1", &set1.power_set_len(), set1.power_set_iter(order))?;
+    write_listing(&mut writer, "Power Set of Set 2", &set2.power_set_len(), set2.power_set_iter(order))?;
+    write_listing(
+        &mut writer,
+        &format!("Subset of Size {} of Set 1", subset_size),
+        &binomial(set1.cardinality(), subset_size),
+        set1.subsets_of_size(subset_size),
+    )?;
+
+    let svg_path = Path::new(file_path).with_extension("svg");
+    fs::write(&svg_path, venn.to_svg())?;
+
+    println!("Results saved to {}", file_path);
+    println!("Venn diagram saved to {}", svg_path.display());
+    // Close the CSV writer
+    writer.flush();
+
+    Ok(())
+}
+
+
+// Abstract syntax tree of a set expression such as `(A ∪ B) ∩ C'`
+#[derive(Debug, Clone, PartialEq)]
+enum SetExpr {
+    Named(String),
+    Union(Box<SetExpr>, Box<SetExpr>),
+    Intersection(Box<SetExpr>, Box<SetExpr>),
+    Difference(Box<SetExpr>, Box<SetExpr>),
+    SymmetricDifference(Box<SetExpr>, Box<SetExpr>),
+    // Complement relative to the universe set named `U`
+    Complement(Box<SetExpr>),
+}
+
+impl SetExpr {
+    fn parse(input: &str) -> Result<SetExpr, String> {
+        let mut parser = SetExprParser { chars: input.chars().collect(), pos: 0 };
+        let expr = parser.parse_expression()?;
+        parser.skip_whitespace();
+        match parser.peek() {
+            None => Ok(expr),
+            Some(c) => Err(format!("Unexpected '{}' at position {}", c, parser.pos)),
+        }
+    }
+
+    fn evaluate<T: Ord + Hash + Clone>(&self, sets: &BTreeMap<String, Set<T>>) -> Result<Set<T>, String> {
+        match self {
+            SetExpr::Named(name) => sets.get(name).cloned().ok_or_else(|| format!("Unknown set '{}'", name)),
+            SetExpr::Union(left, right) => Ok(left.evaluate(sets)?.union(&right.evaluate(sets)?)),
+            SetExpr::Intersection(left, right) => Ok(left.evaluate(sets)?.intersection(&right.evaluate(sets)?)),
+            SetExpr::Difference(left, right) => Ok(left.evaluate(sets)?.difference(&right.evaluate(sets)?)),
+            SetExpr::SymmetricDifference(left, right) => {
+                Ok(left.evaluate(sets)?.symmetric_difference(&right.evaluate(sets)?))
+            }
+            SetExpr::Complement(inner) => {
+                let universe = sets.get("U").ok_or("A complement needs a universe set named 'U'")?;
+                Ok(inner.evaluate(sets)?.complement(universe))
+            }
+        }
+    }
+
+    // Collect the names of the sets used in the expression
+    fn variables(&self, names: &mut BTreeSet<String>) {
+        match self {
+            SetExpr::Named(name) => {
+                names.insert(name.clone());
+            }
+            SetExpr::Union(left, right)
+            | SetExpr::Intersection(left, right)
+            | SetExpr::Difference(left, right)
+            | SetExpr::SymmetricDifference(left, right) => {
+                left.variables(names);
+                right.variables(names);
+            }
+            SetExpr::Complement(inner) => inner.variables(names),
+        }
+    }
+
+    // Whether an element lying in exactly the sets named in `region` belongs to the result;
+    // the universe `U` contains every element
+    fn contains_in_region(&self, region: &BTreeSet<String>) -> bool {
+        match self {
+            SetExpr::Named(name) => name == "U" || region.contains(name),
+            SetExpr::Union(left, right) => left.contains_in_region(region) || right.contains_in_region(region),
+            SetExpr::Intersection(left, right) => left.contains_in_region(region) && right.contains_in_region(region),
+            SetExpr::Difference(left, right) => left.contains_in_region(region) && !right.contains_in_region(region),
+            SetExpr::SymmetricDifference(left, right) => left.contains_in_region(region) != right.contains_in_region(region),
+            SetExpr::Complement(inner) => !inner.contains_in_region(region),
+        }
+    }
+}
+
+impl fmt::Display for SetExpr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SetExpr::Named(name) => write!(f, "{}", name),
+            SetExpr::Union(left, right) => write!(f, "({} ∪ {})", left, right),
+            SetExpr::Intersection(left, right) => write!(f, "({} ∩ {})", left, right),
+            SetExpr::Difference(left, right) => write!(f, "({} − {})", left, right),
+            SetExpr::SymmetricDifference(left, right) => write!(f, "({} Δ {})", left, right),
+            SetExpr::Complement(inner) => write!(f, "{}'", inner),
+        }
+    }
+}
+
+// Recursive-descent parser for set expressions. Intersection binds tighter than union,
+// difference and symmetric difference, which associate to the left; complement binds tightest.
+//
+//   expression := term (("∪" | "|" | "+" | "−" | "-" | "\\" | "Δ" | "^") term)*
+//   term       := factor (("∩" | "&" | "*") factor)*
+//   factor     := ("~" | "¬") factor | primary ("'" | "ᶜ")*
+//   primary    := name | "(" expression ")"
+struct SetExprParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+// Postfix complement marks; `ᶜ` is alphabetic, so names must stop at it explicitly
+const COMPLEMENT_MARKS: [char; 3] = ['\'', '′', 'ᶜ'];
+
+fn is_name_char(c: char) -> bool {
+    (c.is_alphanumeric() || c == '_') && !COMPLEMENT_MARKS.contains(&c)
+}
+
+impl SetExprParser {
+    fn skip_whitespace(&mut self) {
+        while self.chars.get(self.pos).is_some_and(|c| c.is_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.chars.get(self.pos).copied()
+    }
+
+    fn parse_expression(&mut self) -> Result<SetExpr, String> {
+        let mut left = self.parse_term()?;
+        loop {
+            let make: fn(Box<SetExpr>, Box<SetExpr>) -> SetExpr = match self.peek() {
+                Some('∪' | '|' | '+') => SetExpr::Union,
+                Some('−' | '-' | '\\') => SetExpr::Difference,
+                Some('Δ' | '△' | '^') => SetExpr::SymmetricDifference,
+                _ => return Ok(left),
+            };
+            self.pos += 1;
+            let right = self.parse_term()?;
+            left = make(Box::new(left), Box::new(right));
+        }
+    }
+
+    fn parse_term(&mut self) -> Result<SetExpr, String> {
+        let mut left = self.parse_factor()?;
+        while let Some('∩' | '&' | '*') = self.peek() {
+            self.pos += 1;
+            let right = self.parse_factor()?;
+            left = SetExpr::Intersection(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_factor(&mut self) -> Result<SetExpr, String> {
+        if let Some('~' | '¬') = self.peek() {
+            self.pos += 1;
+            return Ok(SetExpr::Complement(Box::new(self.parse_factor()?)));
+        }
+
+        let mut expr = self.parse_primary()?;
+        // Complement marks directly follow their operand
+        while self.chars.get(self.pos).is_some_and(|c| COMPLEMENT_MARKS.contains(c)) {
+            self.pos += 1;
+            expr = SetExpr::Complement(Box::new(expr));
+        }
+        Ok(expr)
+    }
+
+    fn parse_primary(&mut self) -> Result<SetExpr, String> {
+        match self.peek() {
+            Some('(') => {
+                self.pos += 1;
+                let expr = self.parse_expression()?;
+                if self.peek() != Some(')') {
+                    return Err(format!("Expected ')' at position {}", self.pos));
+                }
+                self.pos += 1;
+                Ok(expr)
+            }
+            Some(c) if c.is_alphabetic() && is_name_char(c) => {
+                let start = self.pos;
+                while self.chars.get(self.pos).is_some_and(|&c| is_name_char(c)) {
+                    self.pos += 1;
+                }
+                Ok(SetExpr::Named(self.chars[start..self.pos].iter().collect()))
+            }
+            Some(c) => Err(format!("Unexpected '{}' at position {}", c, self.pos)),
+            None => Err("Unexpected end of expression".to_string()),
+        }
+    }
+}
+
+// Reads the set names, then each named set; `U` is used as the universe for complements
+fn read_named_sets<T>() -> BTreeMap<String, Set<T>>
+where
+    T: Ord + Hash + Clone + FromStr,
+    T::Err: fmt::Debug,
+{
+    println!("Enter the set names (space-separated, e.g. A B C U):");
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).expect("Failed to read line");
+
+    let mut sets = BTreeMap::new();
+    for name in input.split_whitespace() {
+        println!("Set {}:", name);
+        sets.insert(name.to_string(), read_set());
+    }
+    sets
+}
+
+fn set_expression_operations<T>() -> Result<(), Box<dyn Error>>
+where
+    T: Ord + Hash + Clone + fmt::Debug + FromStr,
+    T::Err: fmt::Debug,
+{
+    let sets: BTreeMap<String, Set<T>> = read_named_sets();
+
+    let file_path = "set_expressions.csv";
+    let mut writer = Writer::from_path(file_path)?;
+    writer.write_record(["Expression", "Result"])?;
+
+    loop {
+        println!("Enter a set expression, e.g. (A ∪ B) ∩ C' or A - (B & C) (empty line to finish):");
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        let input = input.trim();
+        if input.is_empty() {
+            break;
+        }
+
+        match SetExpr::parse(input).and_then(|expr| expr.evaluate(&sets).map(|result| (expr, result))) {
+            Ok((expr, result)) => {
+                println!("\n{} = \n{:?}", expr, result.elements);
+                writer.write_record([input, &format!("{:?}", result.elements)])?;
+            }
+            Err(message) => println!("Invalid expression: {}", message),
+        }
+    }
+
+    writer.flush()?;
+    println!("Results saved to {}", file_path);
+    Ok(())
+}
+
+
+// An equation between two set expressions, e.g. `(A ∪ B)' = A' ∩ B'`
+#[derive(Debug, Clone, PartialEq)]
+struct SetIdentity {
+    left: SetExpr,
+    right: SetExpr,
+}
+
+impl SetIdentity {
+    // Splits the input at `=`, `==` or `≡` and parses each side as a set expression
+    fn parse(input: &str) -> Result<SetIdentity, String> {
+        let (left, right) = input
+            .split_once(['=', '≡'])
+            .ok_or("An identity needs two sides separated by '='")?;
+        let right = right.strip_prefix('=').unwrap_or(right);
+
+        if left.trim().is_empty() || right.trim().is_empty() {
+            return Err("Both sides of the identity must be non-empty".to_string());
+        }
+
+        Ok(SetIdentity { left: SetExpr::parse(left)?, right: SetExpr::parse(right)? })
+    }
+
+    // Names of the sets appearing on either side, excluding the universe `U`
+    fn variables(&self) -> Set<String> {
+        let mut names = BTreeSet::new();
+        self.left.variables(&mut names);
+        self.right.variables(&mut names);
+        names.remove("U");
+        Set { elements: names }
+    }
+
+    // Checks every Venn region; two expressions are identical exactly when they agree on
+    // whether each region belongs to them. Returns sets in which they differ, using a single
+    // element placed in the first disagreeing region.
+    fn counterexample(&self) -> Option<BTreeMap<String, Set<i32>>> {
+        let variables = self.variables();
+        let region = variables
+            .power_set_iter(SubsetOrder::Binary)
+            .map(|region| region.into_iter().collect::<BTreeSet<String>>())
+            .find(|region| self.left.contains_in_region(region) != self.right.contains_in_region(region))?;
+
+        let element: Set<i32> = vec![1].into_iter().collect();
+        let mut sets: BTreeMap<String, Set<i32>> = variables
+            .elements
+            .iter()
+            .map(|name| {
+                let set = if region.contains(name) { element.clone() } else { Set::new() };
+                (name.clone(), set)
+            })
+            .collect();
+        sets.insert("U".to_string(), element);
+        Some(sets)
+    }
+}
+
+impl fmt::Display for SetIdentity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} = {}", self.left, self.right)
+    }
+}
+
+fn format_named_sets<T: Ord + Hash + Clone + fmt::Debug>(sets: &BTreeMap<String, Set<T>>) -> String {
+    sets.iter()
+        .map(|(name, set)| format!("{} = {:?}", name, set.elements))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+fn set_identity_operations() -> Result<(), Box<dyn Error>> {
+    let file_path = "set_identities.csv";
+    let mut writer = Writer::from_path(file_path)?;
+    writer.write_record(["Identity", "Holds", "Counterexample"])?;
+
+    loop {
+        println!("Enter a set identity, e.g. (A ∪ B)' = A' ∩ B' (empty line to finish):");
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        let input = input.trim();
+        if input.is_empty() {
+            break;
+        }
+
+        let identity = match SetIdentity::parse(input) {
+            Ok(identity) => identity,
+            Err(message) => {
+                println!("Invalid identity: {}", message);
+                continue;
+            }
+        };
+
+        match identity.counterexample() {
+            None => {
+                println!("\n{} \nholds for all sets", identity);
+                writer.write_record([input, "true", ""])?;
+            }
+            Some(sets) => {
+                let left = identity.left.evaluate(&sets)?;
+                let right = identity.right.evaluate(&sets)?;
+                println!("\n{} \ndoes not hold, counterexample: {}", identity, format_named_sets(&sets));
+                println!("Left side: {:?}, right side: {:?}", left.elements, right.elements);
+                writer.write_record([input, "false", &format_named_sets(&sets)])?;
+            }
+        }
+    }
+
+    writer.flush()?;
+    println!("Results saved to {}", file_path);
+    Ok(())
+}
+
+
+// Binary relation on a set, stored as the set of its ordered pairs
+#[derive(Debug, Clone, PartialEq)]
+struct Relation<T: Ord + Hash + Clone = i32> {
+    domain: Set<T>,
+    pairs: Set<(T, T)>,
+}
+
+impl<T: Ord + Hash + Clone> Relation<T> {
+    fn new(domain: Set<T>, pairs: Set<(T, T)>) -> Result<Self, String> {
+        if pairs.elements.iter().any(|(a, b)| !domain.elements.contains(a) || !domain.elements.contains(b)) {
+            return Err("Every pair must relate elements of the set".to_string());
+        }
+        Ok(Relation { domain, pairs })
+    }
+
+    fn related(&self, a: &T, b: &T) -> bool {
+        self.pairs.elements.contains(&(a.clone(), b.clone()))
+    }
+
+    // Every element is related to itself
+    fn is_reflexive(&self) -> bool {
+        self.domain.elements.iter().all(|a| self.related(a, a))
+    }
+
+    // a R b implies b R a
+    fn is_symmetric(&self) -> bool {
+        self.pairs.elements.iter().all(|(a, b)| self.related(b, a))
+    }
+
+    // a R b and b R a imply a = b
+    fn is_antisymmetric(&self) -> bool {
+        self.pairs.elements.iter().all(|(a, b)| a == b || !self.related(b, a))
+    }
+
+    // a R b and b R c imply a R c
+    fn is_transitive(&self) -> bool {
+        self.pairs.elements.iter().all(|(a, b)| {
+            self.pairs.elements.iter().filter(|(c, _)| c == b).all(|(_, d)| self.related(a, d))
+        })
+    }
+
+    fn is_equivalence(&self) -> bool {
+        self.is_reflexive() && self.is_symmetric() && self.is_transitive()
+    }
+
+    // Blocks of elements related to each other, in order of their smallest element
+    fn equivalence_classes(&self) -> Result<Vec<Set<T>>, String> {
+        if !self.is_equivalence() {
+            return Err("Equivalence classes need an equivalence relation".to_string());
+        }
+
+        let mut classes: Vec<Set<T>> = Vec::new();
+        for a in &self.domain.elements {
+            if classes.iter().all(|class| !class.elements.contains(a)) {
+                classes.push(self.domain.elements.iter().filter(|b| self.related(a, b)).cloned().collect());
+            }
+        }
+        Ok(classes)
+    }
+
+    // Smallest reflexive relation containing this one
+    fn reflexive_closure(&self) -> Relation<T> {
+        let diagonal = self.domain.elements.iter().map(|a| (a.clone(), a.clone())).collect();
+        Relation { domain: self.domain.clone(), pairs: self.pairs.union(&diagonal) }
+    }
+
+    // Smallest symmetric relation containing this one
+    fn symmetric_closure(&self) -> Relation<T> {
+        let inverse = self.pairs.elements.iter().map(|(a, b)| (b.clone(), a.clone())).collect();
+        Relation { domain: self.domain.clone(), pairs: self.pairs.union(&inverse) }
+    }
+
+    // Smallest transitive relation containing this one, computed with Warshall's algorithm
+    #[allow(clippy::needless_range_loop)]
+    fn transitive_closure(&self) -> Relation<T> {
+        let elements = self.domain.to_vec();
+        let mut reach = self.to_boolean_matrix();
+        let n = elements.len();
+        for k in 0..n {
+            for i in 0..n {
+                if reach[i][k] {
+                    for j in 0..n {
+                        reach[i][j] = reach[i][j] || reach[k][j];
+                    }
+                }
+            }
+        }
+        Relation::from_boolean_matrix(&self.domain, &reach)
+    }
+
+    // Entry (i, j) is true when the i-th element is related to the j-th, in ascending order
+    fn to_boolean_matrix(&self) -> Vec<Vec<bool>> {
+        let elements = self.domain.to_vec();
+        elements.iter().map(|a| elements.iter().map(|b| self.related(a, b)).collect()).collect()
+    }
+
+    fn from_boolean_matrix(domain: &Set<T>, matrix: &[Vec<bool>]) -> Relation<T> {
+        let elements = domain.to_vec();
+        let mut pairs = Set::new();
+        for (i, row) in matrix.iter().enumerate() {
+            for (j, &related) in row.iter().enumerate() {
+                if related {
+                    pairs.add((elements[i].clone(), elements[j].clone()));
+                }
+            }
+        }
+        Relation { domain: domain.clone(), pairs }
+    }
+
+    // The boolean matrix as a `Matrix` of ones and zeros
+    fn to_matrix(&self) -> Matrix {
+        let n = self.domain.cardinality();
+        let data = self
+            .to_boolean_matrix()
+            .iter()
+            .map(|row| row.iter().map(|&related| if related { 1.0 } else { 0.0 }).collect())
+            .collect();
+        Matrix::new(n, n, data)
+    }
+}
+
+// Reads a relation as the underlying set followed by its pairs written as `a,b`
+fn read_relation<T>() -> Result<Relation<T>, String>
+where
+    T: Ord + Hash + Clone + FromStr,
+    T::Err: fmt::Debug,
+{
+    let domain: Set<T> = read_set();
+
+    println!("Enter the ordered pairs of the relation (space-separated, each written as a,b):");
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).expect("Failed to read line");
+
+    Relation::new(domain, parse_pairs(&input)?)
+}
+
+// Parses space-separated ordered pairs written as `a,b`
+fn parse_pairs<T, U>(input: &str) -> Result<Set<(T, U)>, String>
+where
+    T: Ord + Hash + Clone + FromStr,
+    U: Ord + Hash + Clone + FromStr,
+{
+    input
+        .split_whitespace()
+        .map(|pair| {
+            let invalid = || format!("Invalid pair `{}`, expected a,b", pair);
+            let (a, b) = pair.split_once(',').ok_or_else(invalid)?;
+            Ok((a.trim().parse().map_err(|_| invalid())?, b.trim().parse().map_err(|_| invalid())?))
+        })
+        .collect()
+}
+
+fn relation_operations<T>() -> Result<(), Box<dyn Error>>
+where
+    T: Ord + Hash + Clone + fmt::Debug + FromStr,
+    T::Err: fmt::Debug,
+{
+    let relation: Relation<T> = read_relation()?;
+
+    println!("\nRelation on {:?}:", relation.domain.elements);
+    relation.to_matrix().display_matrix();
+
+    println!("\nIs it reflexive? \n{}", relation.is_reflexive());
+    println!("\nIs it symmetric? \n{}", relation.is_symmetric());
+    println!("\nIs it antisymmetric? \n{}", relation.is_antisymmetric());
+    println!("\nIs it transitive? \n{}", relation.is_transitive());
+    println!("\nIs it an equivalence relation? \n{}", relation.is_equivalence());
+
+    let classes = relation.equivalence_classes();
+    match &classes {
+        Ok(classes) => {
+            let classes: Vec<_> = classes.iter().map(|class| &class.elements).collect();
+            println!("\nEquivalence classes: \n{:?}", classes);
+        }
+        Err(message) => println!("\nEquivalence classes: \n{}", message),
+    }
+
+    let reflexive_closure = relation.reflexive_closure();
+    let symmetric_closure = relation.symmetric_closure();
+    let transitive_closure = relation.transitive_closure();
+
+    println!("\nReflexive closure: \n{:?}", reflexive_closure.pairs.elements);
+    println!("\nSymmetric closure: \n{:?}", symmetric_closure.pairs.elements);
+    println!("\nTransitive closure: \n{:?}", transitive_closure.pairs.elements);
+    transitive_closure.to_matrix().display_matrix();
+
+    let file_path = "relation_operations.csv";
+    let mut writer = Writer::from_path(file_path)?;
+
+    writer.write_record(["Relation Operation", "Result"])?;
+    writer.write_record(["Relation Matrix", &format!("{:?}", relation.to_matrix().data)])?;
+    writer.write_record(["Is Reflexive", &relation.is_reflexive().to_string()])?;
+    writer.write_record(["Is Symmetric", &relation.is_symmetric().to_string()])?;
+    writer.write_record(["Is Antisymmetric", &relation.is_antisymmetric().to_string()])?;
+    writer.write_record(["Is Transitive", &relation.is_transitive().to_string()])?;
+    writer.write_record(["Is Equivalence", &relation.is_equivalence().to_string()])?;
+    if let Ok(classes) = &classes {
+        for class in classes {
+            writer.write_record(["Equivalence Class", &format!("{:?}", class.elements)])?;
+        }
+    }
+    writer.write_record(["Reflexive Closure", &format!("{:?}", reflexive_closure.to_matrix().data)])?;
+    writer.write_record(["Symmetric Closure", &format!("{:?}", symmetric_closure.to_matrix().data)])?;
+    writer.write_record(["Transitive Closure", &format!("{:?}", transitive_closure.to_matrix().data)])?;
+
+    writer.flush()?;
+    println!("Results saved to {}", file_path);
+    Ok(())
+}
+
+
+fn partition_operations<T>() -> Result<(), Box<dyn Error>>
+where
+    T: Ord + Hash + Clone + fmt::Debug + FromStr,
+    T::Err: fmt::Debug,
+{
+    let set: Set<T> = read_set();
+    let n = set.cardinality();
+
+    println!("\nNumber of partitions (Bell number B({})): \n{}", n, bell_number(n));
+    println!("\nPartitions into k blocks (Stirling numbers S({}, k)):", n);
+    let stirling: Vec<BigUint> = (1..=n).map(|k| stirling_second_kind(n, k)).collect();
+    for (k, count) in (1..=n).zip(&stirling) {
+        println!("k = {}: {}", k, count);
+    }
+
+    let file_path = "set_partitions.csv";
+    let mut writer = Writer::from_path(file_path)?;
+
+    writer.write_record(["Partition Operation", "Result"])?;
+    writer.write_record(["Bell Number", &bell_number(n).to_string()])?;
+    for (k, count) in (1..=n).zip(&stirling) {
+        writer.write_record([&format!("Stirling Number S({}, {})", n, k), &count.to_string()])?;
+    }
+
+    let partitions = set.partitions().map(|partition| partition.into_iter().map(|block| block.elements).collect::<Vec<_>>());
+    write_listing(&mut writer, "Partition", &bell_number(n), partitions)?;
+
+    writer.flush()?;
+    println!("Partitions saved to {}", file_path);
+    Ok(())
+}
+
+
+// Function from a domain set to a codomain set, given by its (input, output) pairs.
+// The pairs are not required to form a function so that `is_well_defined` can be checked.
+#[derive(Debug, Clone, PartialEq)]
+struct Mapping<T: Ord + Hash + Clone = i32, U: Ord + Hash + Clone = T> {
+    domain: Set<T>,
+    codomain: Set<U>,
+    pairs: Set<(T, U)>,
+}
+
+impl<T: Ord + Hash + Clone, U: Ord + Hash + Clone> Mapping<T, U> {
+    fn new(domain: Set<T>, codomain: Set<U>, pairs: Set<(T, U)>) -> Self {
+        Mapping { domain, codomain, pairs }
+    }
+
+    // Reads the pairs from a CSV file with one `input,output` row per pair and no header
+    fn from_csv(domain: Set<T>, codomain: Set<U>, file_path: &str) -> Result<Self, Box<dyn Error>>
+    where
+        T: FromStr,
+        T::Err: Error + 'static,
+        U: FromStr,
+        U::Err: Error + 'static,
+    {
+        let mut reader = ReaderBuilder::new().has_headers(false).from_path(file_path)?;
+        let mut pairs = Set::new();
+        for record in reader.records() {
+            let record = record?;
+            if record.len() != 2 {
+                return Err(format!("Expected two values per row, found {}", record.len()).into());
+            }
+            pairs.add((record[0].trim().parse()?, record[1].trim().parse()?));
+        }
+        Ok(Mapping::new(domain, codomain, pairs))
+    }
+
+    // Image of an element, if the mapping sends it anywhere
+    fn apply(&self, x: &T) -> Option<&U> {
+        self.pairs.elements.iter().find(|(a, _)| a == x).map(|(_, b)| b)
+    }
+
+    // Every domain element has exactly one image, and that image lies in the codomain
+    fn is_well_defined(&self) -> bool {
+        self.pairs.elements.iter().all(|(a, b)| self.domain.elements.contains(a) && self.codomain.elements.contains(b))
+            && self.domain.elements.iter().all(|x| self.pairs.elements.iter().filter(|(a, _)| a == x).count() == 1)
+    }
+
+    // No two domain elements share an image
+    fn is_injective(&self) -> bool {
+        let images: Set<U> = self.pairs.elements.iter().map(|(_, b)| b.clone()).collect();
+        self.is_well_defined() && images.cardinality() == self.domain.cardinality()
+    }
+
+    // Every codomain element is an image
+    fn is_surjective(&self) -> bool {
+        let images: Set<U> = self.pairs.elements.iter().map(|(_, b)| b.clone()).collect();
+        self.is_well_defined() && images.equal_sets(&self.codomain)
+    }
+
+    fn is_bijective(&self) -> bool {
+        self.is_injective() && self.is_surjective()
+    }
+
+    // The composition `other ∘ self`, which first applies this mapping and then `other`
+    fn compose<V: Ord + Hash + Clone>(&self, other: &Mapping<U, V>) -> Result<Mapping<T, V>, String> {
+        if !self.is_well_defined() || !other.is_well_defined() {
+            return Err("Both functions must be well-defined".to_string());
+        }
+        if !self.codomain.is_subset(&other.domain) {
+            return Err("The codomain of the first function must be part of the domain of the second".to_string());
+        }
+
+        let pairs = self
+            .pairs
+            .elements
+            .iter()
+            .filter_map(|(a, b)| other.apply(b).map(|c| (a.clone(), c.clone())))
+            .collect();
+        Ok(Mapping::new(self.domain.clone(), other.codomain.clone(), pairs))
+    }
+
+    // The inverse function, which only exists for bijections
+    fn inverse(&self) -> Result<Mapping<U, T>, String> {
+        if !self.is_bijective() {
+            return Err("Only a bijective function has an inverse".to_string());
+        }
+
+        let pairs = self.pairs.elements.iter().map(|(a, b)| (b.clone(), a.clone())).collect();
+        Ok(Mapping::new(self.codomain.clone(), self.domain.clone(), pairs))
+    }
+}
+
+// Reads the domain, the codomain and then the pairs, either typed in or from a CSV file
+fn read_mapping<T>() -> Result<Mapping<T>, Box<dyn Error>>
+where
+    T: Ord + Hash + Clone + FromStr,
+    T::Err: Error + 'static,
+{
+    println!("Domain:");
+    let domain: Set<T> = read_set();
+    println!("Codomain:");
+    let codomain: Set<T> = read_set();
+
+    println!("Enter the pairs of the function (space-separated, each written as a,b), or the path of a CSV file with one pair per row:");
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    let input = input.trim();
+
+    if input.ends_with(".csv") {
+        Mapping::from_csv(domain, codomain, input)
+    } else {
+        Ok(Mapping::new(domain, codomain, parse_pairs(input)?))
+    }
+}
+
+fn mapping_operations<T>() -> Result<(), Box<dyn Error>>
+where
+    T: Ord + Hash + Clone + fmt::Debug + FromStr,
+    T::Err: Error + 'static,
+{
+    println!("Enter the function f:");
+    let mapping: Mapping<T> = read_mapping()?;
+
+    println!("\nf = \n{:?}", mapping.pairs.elements);
+    println!("\nIs f well-defined? \n{}", mapping.is_well_defined());
+    println!("\nIs f injective? \n{}", mapping.is_injective());
+    println!("\nIs f surjective? \n{}", mapping.is_surjective());
+    println!("\nIs f bijective? \n{}", mapping.is_bijective());
+
+    let inverse = mapping.inverse().map(|inverse| format!("{:?}", inverse.pairs.elements));
+    match &inverse {
+        Ok(pairs) => println!("\nInverse of f: \n{}", pairs),
+        Err(message) => println!("\nInverse of f: \n{}", message),
+    }
+
+    println!("Compose f with a second function g? (true or false)");
+    let composition = if read_boolean_input() {
+        println!("Enter the function g:");
+        let other: Mapping<T> = read_mapping()?;
+        let composition = mapping.compose(&other).map(|composition| format!("{:?}", composition.pairs.elements));
+        match &composition {
+            Ok(pairs) => println!("\ng ∘ f = \n{}", pairs),
+            Err(message) => println!("\ng ∘ f: \n{}", message),
+        }
+        Some(composition)
+    } else {
+        None
+    };
+
+    let file_path = "mapping_operations.csv";
+    let mut writer = Writer::from_path(file_path)?;
+
+    writer.write_record(["Function Operation", "Result"])?;
+    writer.write_record(["Function", &format!("{:?}", mapping.pairs.elements)])?;
+    writer.write_record(["Is Well-Defined", &mapping.is_well_defined().to_string()])?;
+    writer.write_record(["Is Injective", &mapping.is_injective().to_string()])?;
+    writer.write_record(["Is Surjective", &mapping.is_surjective().to_string()])?;
+    writer.write_record(["Is Bijective", &mapping.is_bijective().to_string()])?;
+    writer.write_record(["Inverse", inverse.as_ref().unwrap_or_else(|message| message)])?;
+    if let Some(composition) = &composition {
+        writer.write_record(["Composition", composition.as_ref().unwrap_or_else(|message| message)])?;
+    }
+
+    writer.flush()?;
+    println!("Results saved to {}", file_path);
+    Ok(())
+}
+
+
+// Partially ordered set: a reflexive, antisymmetric and transitive relation
+#[derive(Debug, Clone, PartialEq)]
+struct Poset<T: Ord + Hash + Clone = i32> {
+    order: Relation<T>,
+}
+
+impl Poset<i32> {
+    // Integers ordered by divisibility, a ≤ b when a divides b
+    fn divisibility(set: &Set<i32>) -> Result<Poset<i32>, String> {
+        let divides = |a: i32, b: i32| if a == 0 { b == 0 } else { b.wrapping_rem(a) == 0 };
+        let pairs = set
+            .elements
+            .iter()
+            .flat_map(|&a| set.elements.iter().map(move |&b| (a, b)))
+            .filter(|&(a, b)| divides(a, b))
+            .collect();
+        Poset::new(Relation::new(set.clone(), pairs)?)
+    }
+}
+
+// The inclusion order on n elements relates 3^n pairs of the 2^n subsets, and analysing it
+// slows down quickly, so n is kept small
+const MAX_SUBSET_INCLUSION_LEN: usize = 5;
+
+impl<T: Ord + Hash + Clone> Poset<Set<T>> {
+    // The power set of a set ordered by inclusion
+    fn subset_inclusion(set: &Set<T>) -> Result<Poset<Set<T>>, String> {
+        if set.cardinality() > MAX_SUBSET_INCLUSION_LEN {
+            return Err(format!(
+                "Subset inclusion is limited to sets of at most {} elements, found {}",
+                MAX_SUBSET_INCLUSION_LEN,
+                set.cardinality()
+            ));
+        }
+        let subsets: Set<Set<T>> = set.power_set_iter(SubsetOrder::Binary).map(|subset| subset.into_iter().collect()).collect();
+        let pairs = subsets
+            .elements
+            .iter()
+            .flat_map(|a| subsets.elements.iter().map(move |b| (a.clone(), b.clone())))
+            .filter(|(a, b)| a.is_subset(b))
+            .collect();
+        Ok(Poset { order: Relation { domain: subsets, pairs } })
+    }
+}
+
+impl<T: Ord + Hash + Clone> Poset<T> {
+    fn new(order: Relation<T>) -> Result<Poset<T>, String> {
+        if !order.is_reflexive() || !order.is_antisymmetric() || !order.is_transitive() {
+            return Err("A partial order must be reflexive, antisymmetric and transitive".to_string());
+        }
+        Ok(Poset { order })
+    }
+
+    // Partial order generated by the given pairs, e.g. just the covering pairs of a Hasse diagram
+    fn from_pairs(domain: Set<T>, pairs: Set<(T, T)>) -> Result<Poset<T>, String> {
+        Poset::new(Relation::new(domain, pairs)?.reflexive_closure().transitive_closure())
+    }
+
+    fn elements(&self) -> &BTreeSet<T> {
+        &self.order.domain.elements
+    }
+
+    fn leq(&self, a: &T, b: &T) -> bool {
+        self.order.related(a, b)
+    }
+
+    // Elements with nothing strictly below them
+    fn minimal_elements(&self) -> Set<T> {
+        self.elements().iter().filter(|a| self.elements().iter().all(|b| b == *a || !self.leq(b, a))).cloned().collect()
+    }
+
+    // Elements with nothing strictly above them
+    fn maximal_elements(&self) -> Set<T> {
+        self.elements().iter().filter(|a| self.elements().iter().all(|b| b == *a || !self.leq(a, b))).cloned().collect()
+    }
+
+    // Element below every other element
+    fn least_element(&self) -> Option<T> {
+        self.elements().iter().find(|a| self.elements().iter().all(|b| self.leq(a, b))).cloned()
+    }
+
+    // Element above every other element
+    fn greatest_element(&self) -> Option<T> {
+        self.elements().iter().find(|a| self.elements().iter().all(|b| self.leq(b, a))).cloned()
+    }
+
+    // Elements above every member of `subset`
+    fn upper_bounds(&self, subset: &Set<T>) -> Set<T> {
+        self.elements().iter().filter(|u| subset.elements.iter().all(|a| self.leq(a, u))).cloned().collect()
+    }
+
+    // Elements below every member of `subset`
+    fn lower_bounds(&self, subset: &Set<T>) -> Set<T> {
+        self.elements().iter().filter(|l| subset.elements.iter().all(|a| self.leq(l, a))).cloned().collect()
+    }
+
+    // Least upper bound of two elements
+    fn join(&self, a: &T, b: &T) -> Option<T> {
+        let bounds = Poset { order: self.restrict(&self.upper_bounds(&vec![a.clone(), b.clone()].into_iter().collect())) };
+        bounds.least_element()
+    }
+
+    // Greatest lower bound of two elements
+    fn meet(&self, a: &T, b: &T) -> Option<T> {
+        let bounds = Poset { order: self.restrict(&self.lower_bounds(&vec![a.clone(), b.clone()].into_iter().collect())) };
+        bounds.greatest_element()
+    }
+
+    // The order relation limited to a subset of the elements
+    fn restrict(&self, subset: &Set<T>) -> Relation<T> {
+        let pairs = self
+            .order
+            .pairs
+            .elements
+            .iter()
+            .filter(|(a, b)| subset.elements.contains(a) && subset.elements.contains(b))
+            .cloned()
+            .collect();
+        Relation { domain: subset.clone(), pairs }
+    }
+
+    // Every pair of elements has a join and a meet
+    fn is_lattice(&self) -> bool {
+        self.elements()
+            .iter()
+            .all(|a| self.elements().iter().all(|b| self.join(a, b).is_some() && self.meet(a, b).is_some()))
+    }
+
+    // Pairs a < b with no element strictly between them; these are the edges of the Hasse diagram
+    fn covering_pairs(&self) -> Vec<(T, T)> {
+        self.order
+            .pairs
+            .elements
+            .iter()
+            .filter(|(a, b)| {
+                a != b && !self.elements().iter().any(|c| c != a && c != b && self.leq(a, c) && self.leq(c, b))
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+impl<T: Ord + Hash + Clone + fmt::Debug> Poset<T> {
+    // Hasse diagram in Graphviz DOT format, drawn bottom to top
+    fn to_dot(&self) -> String {
+        let elements: Vec<&T> = self.elements().iter().collect();
+        let node = |x: &T| elements.iter().position(|e| *e == x).unwrap_or(0);
+
+        let mut dot = String::from("digraph Hasse {\n    rankdir=BT;\n    node [shape=plaintext];\n    edge [arrowhead=none];\n");
+        for (i, element) in elements.iter().enumerate() {
+            let label = format!("{:?}", element).replace('\\', "\\\\").replace('"', "\\\"");
+            dot.push_str(&format!("    n{} [label=\"{}\"];\n", i, label));
+        }
+        for (a, b) in self.covering_pairs() {
+            dot.push_str(&format!("    n{} -> n{};\n", node(&a), node(&b)));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn poset_operations() -> Result<(), Box<dyn Error>> {
+    println!("Select the partial order:");
+    println!("1. Divisibility on a set of integers");
+    println!("2. Subset inclusion on the power set of a set");
+    println!("3. Enter the order pairs yourself");
+
+    let mut choice = String::new();
+    io::stdin().read_line(&mut choice)?;
+
+    match choice.trim() {
+        "1" => typed_poset_operations(Poset::divisibility(&read_set())?),
+        "2" => match read_element_type()? {
+            ElementType::Integer => typed_poset_operations(Poset::subset_inclusion(&read_set::<i32>())?),
+            ElementType::Text => typed_poset_operations(Poset::subset_inclusion(&read_set::<String>())?),
+            ElementType::Float => typed_poset_operations(Poset::subset_inclusion(&read_set::<OrderedF64>())?),
+        },
+        "3" => match read_element_type()? {
+            ElementType::Integer => typed_poset_operations(read_poset::<i32>()?),
+            ElementType::Text => typed_poset_operations(read_poset::<String>()?),
+            ElementType::Float => typed_poset_operations(read_poset::<OrderedF64>()?),
+        },
+        _ => Err("Invalid choice".into()),
+    }
+}
+
+// Reads a set and pairs a,b meaning a ≤ b; reflexive and transitive pairs are added automatically
+fn read_poset<T>() -> Result<Poset<T>, String>
+where
+    T: Ord + Hash + Clone + FromStr,
+    T::Err: fmt::Debug,
+{
+    let domain: Set<T> = read_set();
+
+    println!("Enter the pairs a,b with a ≤ b (space-separated, covering pairs are enough):");
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).expect("Failed to read line");
+
+    Poset::from_pairs(domain, parse_pairs(&input)?)
+}
+
+fn typed_poset_operations<T>(poset: Poset<T>) -> Result<(), Box<dyn Error>>
+where
+    T: Ord + Hash + Clone + fmt::Debug,
+{
+    let minimal = poset.minimal_elements();
+    let maximal = poset.maximal_elements();
+    let least = poset.least_element();
+    let greatest = poset.greatest_element();
+
+    println!("\nElements: \n{:?}", poset.order.domain);
+    println!("\nMinimal elements: \n{:?}", minimal);
+    println!("\nMaximal elements: \n{:?}", maximal);
+    println!("\nLeast element: \n{:?}", least);
+    println!("\nGreatest element: \n{:?}", greatest);
+    println!("\nIs it a lattice? \n{}", poset.is_lattice());
+
+    let file_path = "poset_operations.csv";
+    let mut writer = Writer::from_path(file_path)?;
+
+    writer.write_record(["Poset Operation", "Result"])?;
+    writer.write_record(["Minimal Elements", &format!("{:?}", minimal)])?;
+    writer.write_record(["Maximal Elements", &format!("{:?}", maximal)])?;
+    writer.write_record(["Least Element", &format!("{:?}", least)])?;
+    writer.write_record(["Greatest Element", &format!("{:?}", greatest)])?;
+    writer.write_record(["Is Lattice", &poset.is_lattice().to_string()])?;
+
+    println!("\nBounds, joins and meets of each pair:");
+    let elements = poset.order.domain.to_vec();
+    for (i, a) in elements.iter().enumerate() {
+        for b in &elements[i + 1..] {
+            let pair: Set<T> = vec![a.clone(), b.clone()].into_iter().collect();
+            let upper = poset.upper_bounds(&pair);
+            let lower = poset.lower_bounds(&pair);
+            let join = poset.join(a, b);
+            let meet = poset.meet(a, b);
+            println!(
+                "{:?}, {:?}: upper bounds {:?}, lower bounds {:?}, join {:?}, meet {:?}",
+                a, b, upper, lower, join, meet
+            );
+            writer.write_record([&format!("Upper Bounds of {:?}", pair), &format!("{:?}", upper)])?;
+            writer.write_record([&format!("Lower Bounds of {:?}", pair), &format!("{:?}", lower)])?;
+            writer.write_record([&format!("Join of {:?}", pair), &format!("{:?}", join)])?;
+            writer.write_record([&format!("Meet of {:?}", pair), &format!("{:?}", meet)])?;
+        }
+    }
+
+    writer.flush()?;
+
+    let dot_path = "hasse_diagram.dot";
+    fs::write(dot_path, poset.to_dot())?;
+
+    println!("Results saved to {}", file_path);
+    println!("Hasse diagram saved to {}", dot_path);
+    Ok(())
+}
+
+
+// Multiset (bag): like a set, but each element carries how many times it occurs
+#[derive(Debug, Clone, PartialEq)]
+struct Multiset<T: Ord + Hash + Clone = i32> {
+    // Only elements that occur at least once are stored
+    counts: BTreeMap<T, usize>,
+}
+
+impl<T: Ord + Hash + Clone> Multiset<T> {
+    fn new() -> Self {
+        Multiset { counts: BTreeMap::new() }
+    }
+
+    fn add(&mut self, element: T) {
+        self.add_many(element, 1);
+    }
+
+    fn add_many(&mut self, element: T, count: usize) {
+        if count > 0 {
+            *self.counts.entry(element).or_insert(0) += count;
+        }
+    }
+
+    // Removes a single occurrence of the element
+    fn remove(&mut self, element: &T) {
+        if let Some(count) = self.counts.get_mut(element) {
+            *count -= 1;
+            if *count == 0 {
+                self.counts.remove(element);
+            }
+        }
+    }
+
+    // Multiplicity of an element, zero when it does not occur
+    fn count(&self, element: &T) -> usize {
+        self.counts.get(element).copied().unwrap_or(0)
+    }
+
+    // Total number of occurrences
+    fn cardinality(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    // Combines two multisets, choosing each element's multiplicity from both
+    fn combine(&self, other: &Multiset<T>, multiplicity: impl Fn(usize, usize) -> usize) -> Multiset<T> {
+        let mut result = Multiset::new();
+        for element in self.counts.keys().chain(other.counts.keys()) {
+            if !result.counts.contains_key(element) {
+                result.add_many(element.clone(), multiplicity(self.count(element), other.count(element)));
+            }
+        }
+        result
+    }
+
+    // Larger multiplicity of each element
+    fn union(&self, other: &Multiset<T>) -> Multiset<T> {
+        self.combine(other, usize::max)
+    }
+
+    // Smaller multiplicity of each element
+    fn intersection(&self, other: &Multiset<T>) -> Multiset<T> {
+        self.combine(other, usize::min)
+    }
+
+    // Multiplicities added together
+    fn sum(&self, other: &Multiset<T>) -> Multiset<T> {
+        self.combine(other, |a, b| a + b)
+    }
+
+    // Multiplicities subtracted, stopping at zero
+    fn difference(&self, other: &Multiset<T>) -> Multiset<T> {
+        self.combine(other, usize::saturating_sub)
+    }
+
+    fn is_submultiset(&self, other: &Multiset<T>) -> bool {
+        self.counts.iter().all(|(element, &count)| count <= other.count(element))
+    }
+
+    // The distinct elements, dropping multiplicities
+    fn to_set(&self) -> Set<T> {
+        self.counts.keys().cloned().collect()
+    }
+}
+
+impl<T: Ord + Hash + Clone> From<Set<T>> for Multiset<T> {
+    fn from(set: Set<T>) -> Self {
+        set.elements.into_iter().collect()
+    }
+}
+
+impl<T: Ord + Hash + Clone> FromIterator<T> for Multiset<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut multiset = Multiset::new();
+        for element in iter {
+            multiset.add(element);
+        }
+        multiset
+    }
+}
+
+// Reads one line of space-separated elements, counting repeated ones
+fn read_multiset<T>() -> Multiset<T>
+where
+    T: Ord + Hash + Clone + FromStr,
+    T::Err: fmt::Debug,
+{
+    println!("Enter elements of the multiset (space-separated, repeats are counted):");
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).expect("Failed to read line");
+    input
+        .split_whitespace()
+        .map(|s| s.parse().expect("Invalid input"))
+        .collect()
+}
+
+fn multiset_operations() -> Result<(), Box<dyn Error>> {
+    match read_element_type()? {
+        ElementType::Integer => typed_multiset_operations::<i32>(),
+        ElementType::Text => typed_multiset_operations::<String>(),
+        ElementType::Float => typed_multiset_operations::<OrderedF64>(),
+    }
+}
+
+fn typed_multiset_operations<T>() -> Result<(), Box<dyn Error>>
+where
+    T: Ord + Hash + Clone + fmt::Debug + FromStr,
+    T::Err: fmt::Debug,
+{
+    let multiset1: Multiset<T> = read_multiset();
+    let multiset2: Multiset<T> = read_multiset();
+
+    let union = multiset1.union(&multiset2);
+    let intersection = multiset1.intersection(&multiset2);
+    let sum = multiset1.sum(&multiset2);
+    let difference = multiset1.difference(&multiset2);
+    let support1 = multiset1.to_set();
+    let support2 = multiset2.to_set();
+
+    println!("\nMultiset 1 (element: count): \n{:?}", multiset1.counts);
+    println!("\nMultiset 2 (element: count): \n{:?}", multiset2.counts);
+    println!("\nCardinality of Multiset 1: \n{}", multiset1.cardinality());
+    println!("\nCardinality of Multiset 2: \n{}", multiset2.cardinality());
+    println!("\nMultiset 1 is Submultiset of Multiset 2? \n{}", multiset1.is_submultiset(&multiset2));
+
+    println!("\nUnion (max): \n{:?}", union.counts);
+    println!("\nIntersection (min): \n{:?}", intersection.counts);
+    println!("\nSum: \n{:?}", sum.counts);
+    println!("\nDifference: \n{:?}", difference.counts);
+    println!("\nSet of Multiset 1: \n{:?}", support1);
+    println!("\nSet of Multiset 2: \n{:?}", support2);
+
+    println!("-------------Adding and Removing Elements--------------");
+
+    let mut multiset = multiset1.clone();
+    println!("Enter element to add to Multiset 1:");
+    let add_element: T = read_element();
+    multiset.add(add_element.clone());
+    println!("Multiset after adding {:?}: {:?}", add_element, multiset.counts);
+
+    println!("Enter element to remove one occurrence of:");
+    let remove_element: T = read_element();
+    multiset.remove(&remove_element);
+    println!("Multiset after removing {:?}: {:?}", remove_element, multiset.counts);
+
+    let file_path = "multiset_operations.csv";
+    let mut writer = Writer::from_path(file_path)?;
+
+    writer.write_record(["Multiset Operation", "Result"])?;
+    writer.write_record(["Cardinality of Multiset 1", &multiset1.cardinality().to_string()])?;
+    writer.write_record(["Cardinality of Multiset 2", &multiset2.cardinality().to_string()])?;
+    writer.write_record(["Is Submultiset", &multiset1.is_submultiset(&multiset2).to_string()])?;
+    writer.write_record(["Union", &format!("{:?}", union.counts)])?;
+    writer.write_record(["Intersection", &format!("{:?}", intersection.counts)])?;
+    writer.write_record(["Sum", &format!("{:?}", sum.counts)])?;
+    writer.write_record(["Difference", &format!("{:?}", difference.counts)])?;
+    writer.write_record(["Set of Multiset 1", &format!("{:?}", support1)])?;
+    writer.write_record(["Set of Multiset 2", &format!("{:?}", support2)])?;
+
+    writer.flush()?;
+    println!("Results saved to {}", file_path);
+    Ok(())
+}
+
+
+// Pair of a t-norm (fuzzy intersection) and its dual t-conorm (fuzzy union)
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FuzzyOperator {
+    // min and max
+    Standard,
+    // product and probabilistic sum
+    Algebraic,
+    // Łukasiewicz: max(0, a + b - 1) and min(1, a + b)
+    Bounded,
+    // min when one degree is 1, otherwise 0
+    Drastic,
+    // Yager family with parameter w > 0; w = 1 is the bounded pair, w → ∞ the standard one
+    Yager(f64),
+    // Hamacher family with parameter γ ≥ 0; γ = 1 is the algebraic pair
+    Hamacher(f64),
+}
+
+impl FuzzyOperator {
+    fn t_norm(&self, a: f64, b: f64) -> f64 {
+        match *self {
+            FuzzyOperator::Standard => a.min(b),
+            FuzzyOperator::Algebraic => a * b,
+            FuzzyOperator::Bounded => (a + b - 1.0).max(0.0),
+            FuzzyOperator::Drastic => {
+                if a == 1.0 {
+                    b
+                } else if b == 1.0 {
+                    a
+                } else {
+                    0.0
+                }
+            }
+            FuzzyOperator::Yager(w) => (1.0 - ((1.0 - a).powf(w) + (1.0 - b).powf(w)).powf(1.0 / w)).max(0.0),
+            FuzzyOperator::Hamacher(gamma) => {
+                let denominator = gamma + (1.0 - gamma) * (a + b - a * b);
+                if denominator == 0.0 { 0.0 } else { a * b / denominator }
+            }
+        }
+    }
+
+    // Dual of the t-norm under the standard complement, S(a, b) = 1 - T(1 - a, 1 - b);
+    // the simple pairs use their closed forms to avoid rounding
+    fn t_conorm(&self, a: f64, b: f64) -> f64 {
+        match *self {
+            FuzzyOperator::Standard => a.max(b),
+            FuzzyOperator::Algebraic => a + b - a * b,
+            FuzzyOperator::Bounded => (a + b).min(1.0),
+            FuzzyOperator::Yager(w) => (a.powf(w) + b.powf(w)).powf(1.0 / w).min(1.0),
+            _ => 1.0 - self.t_norm(1.0 - a, 1.0 - b),
+        }
+    }
+}
+
+// Fuzzy set: each element of the universe of discourse has a membership degree in [0, 1]
+#[derive(Debug, Clone, PartialEq)]
+struct FuzzySet<T: Ord + Hash + Clone = i32> {
+    memberships: BTreeMap<T, f64>,
+}
+
+impl<T: Ord + Hash + Clone> FuzzySet<T> {
+    fn new() -> Self {
+        FuzzySet { memberships: BTreeMap::new() }
+    }
+
+    fn set_membership(&mut self, element: T, degree: f64) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&degree) {
+            return Err(format!("Membership degree {} is not between 0 and 1", degree));
+        }
+        self.memberships.insert(element, degree);
+        Ok(())
+    }
+
+    // Degree of an element, zero when it is not listed
+    fn membership(&self, element: &T) -> f64 {
+        self.memberships.get(element).copied().unwrap_or(0.0)
+    }
+
+    // Applies a binary operation to the degrees of every element listed in either set
+    fn combine(&self, other: &FuzzySet<T>, operation: impl Fn(f64, f64) -> f64) -> FuzzySet<T> {
+        let memberships = self
+            .memberships
+            .keys()
+            .chain(other.memberships.keys())
+            .map(|element| (element.clone(), operation(self.membership(element), other.membership(element))))
+            .collect();
+        FuzzySet { memberships }
+    }
+
+    fn union(&self, other: &FuzzySet<T>, operator: FuzzyOperator) -> FuzzySet<T> {
+        self.combine(other, |a, b| operator.t_conorm(a, b))
+    }
+
+    fn intersection(&self, other: &FuzzySet<T>, operator: FuzzyOperator) -> FuzzySet<T> {
+        self.combine(other, |a, b| operator.t_norm(a, b))
+    }
+
+    // Standard complement 1 - μ over the listed elements
+    fn complement(&self) -> FuzzySet<T> {
+        let memberships = self.memberships.iter().map(|(element, degree)| (element.clone(), 1.0 - degree)).collect();
+        FuzzySet { memberships }
+    }
+
+    // Crisp set of the elements with degree at least alpha
+    fn alpha_cut(&self, alpha: f64) -> Set<T> {
+        self.memberships.iter().filter(|(_, &degree)| degree >= alpha).map(|(element, _)| element.clone()).collect()
+    }
+
+    // Crisp set of the elements with degree strictly above alpha
+    fn strong_alpha_cut(&self, alpha: f64) -> Set<T> {
+        self.memberships.iter().filter(|(_, &degree)| degree > alpha).map(|(element, _)| element.clone()).collect()
+    }
+
+    // Largest membership degree
+    fn height(&self) -> f64 {
+        self.memberships.values().copied().fold(0.0, f64::max)
+    }
+}
+
+impl<T: Ord + Hash + Clone + Into<f64>> FuzzySet<T> {
+    // Centre of gravity, Σ x·μ(x) / Σ μ(x); undefined when every degree is zero
+    fn centroid(&self) -> Option<f64> {
+        let total: f64 = self.memberships.values().sum();
+        if total == 0.0 {
+            return None;
+        }
+        let weighted: f64 = self.memberships.iter().map(|(element, degree)| element.clone().into() * degree).sum();
+        Some(weighted / total)
+    }
+
+    // Average of the elements that reach the largest degree
+    fn mean_of_maxima(&self) -> Option<f64> {
+        let height = self.height();
+        if height == 0.0 {
+            return None;
+        }
+        let maxima: Vec<f64> = self.alpha_cut(height).elements.into_iter().map(Into::into).collect();
+        Some(maxima.iter().sum::<f64>() / maxima.len() as f64)
+    }
+}
+
+// Reads elements with their degrees written as `element:degree`
+fn parse_fuzzy_set<T>(input: &str) -> Result<FuzzySet<T>, String>
+where
+    T: Ord + Hash + Clone + FromStr,
+{
+    let mut fuzzy_set = FuzzySet::new();
+    for entry in input.split_whitespace() {
+        let invalid = || format!("Invalid entry `{}`, expected element:degree", entry);
+        let (element, degree) = entry.split_once(':').ok_or_else(invalid)?;
+        let degree: f64 = degree.parse().map_err(|_| invalid())?;
+        fuzzy_set.set_membership(element.parse().map_err(|_| invalid())?, degree)?;
+    }
+    Ok(fuzzy_set)
+}
+
+// Asks again until every entry parses and every degree lies in [0, 1]
+fn read_fuzzy_set<T>() -> FuzzySet<T>
+where
+    T: Ord + Hash + Clone + FromStr,
+{
+    loop {
+        println!("Enter elements with membership degrees (space-separated, each written as element:degree, e.g. 1:0.2 2:0.7):");
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).expect("Failed to read line");
+        match parse_fuzzy_set(&input) {
+            Ok(fuzzy_set) => return fuzzy_set,
+            Err(e) => println!("{}. Please try again.", e),
+        }
+    }
+}
+
+fn read_fuzzy_operator() -> Result<FuzzyOperator, Box<dyn Error>> {
+    println!("Select the t-norm and t-conorm:");
+    println!("1. Standard (min / max)");
+    println!("2. Algebraic (product / probabilistic sum)");
+    println!("3. Bounded (Łukasiewicz)");
+    println!("4. Drastic");
+    println!("5. Yager (parametric)");
+    println!("6. Hamacher (parametric)");
+
+    let mut choice = String::new();
+    io::stdin().read_line(&mut choice)?;
+
+    match choice.trim() {
+        "1" => Ok(FuzzyOperator::Standard),
+        "2" => Ok(FuzzyOperator::Algebraic),
+        "3" => Ok(FuzzyOperator::Bounded),
+        "4" => Ok(FuzzyOperator::Drastic),
+        "5" => {
+            println!("Enter the Yager parameter w (greater than 0):");
+            let w = read_f64_input();
+            if w <= 0.0 {
+                return Err("The Yager parameter must be greater than 0".into());
+            }
+            Ok(FuzzyOperator::Yager(w))
+        }
+        "6" => {
+            println!("Enter the Hamacher parameter γ (at least 0):");
+            let gamma = read_f64_input();
+            if gamma < 0.0 {
+                return Err("The Hamacher parameter must be at least 0".into());
+            }
+            Ok(FuzzyOperator::Hamacher(gamma))
+        }
+        _ => Err("Invalid choice".into()),
+    }
+}
+
+fn fuzzy_set_operations<T>() -> Result<(), Box<dyn Error>>
+where
+    T: Ord + Hash + Clone + fmt::Debug + FromStr + Into<f64>,
+    T::Err: fmt::Debug,
+{
+    let fuzzy_set1: FuzzySet<T> = read_fuzzy_set();
+    let fuzzy_set2: FuzzySet<T> = read_fuzzy_set();
+    let operator = read_fuzzy_operator()?;
+    println!("Enter alpha for the alpha-cuts (between 0 and 1):");
+    let alpha = read_f64_input();
+    if !(0.0..=1.0).contains(&alpha) {
+        return Err("Alpha must be between 0 and 1".into());
+    }
+
+    let standard_union = fuzzy_set1.union(&fuzzy_set2, FuzzyOperator::Standard);
+    let standard_intersection = fuzzy_set1.intersection(&fuzzy_set2, FuzzyOperator::Standard);
+    let union = fuzzy_set1.union(&fuzzy_set2, operator);
+    let intersection = fuzzy_set1.intersection(&fuzzy_set2, operator);
+    let complement1 = fuzzy_set1.complement();
+    let complement2 = fuzzy_set2.complement();
+    let alpha_cut1 = fuzzy_set1.alpha_cut(alpha);
+    let alpha_cut2 = fuzzy_set2.alpha_cut(alpha);
+    let strong_alpha_cut1 = fuzzy_set1.strong_alpha_cut(alpha);
+    let strong_alpha_cut2 = fuzzy_set2.strong_alpha_cut(alpha);
+
+    println!("\nFuzzy Set 1: \n{:?}", fuzzy_set1.memberships);
+    println!("\nFuzzy Set 2: \n{:?}", fuzzy_set2.memberships);
+    println!("\nStandard Union (max): \n{:?}", standard_union.memberships);
+    println!("\nStandard Intersection (min): \n{:?}", standard_intersection.memberships);
+    println!("\nUnion ({:?} t-conorm): \n{:?}", operator, union.memberships);
+    println!("\nIntersection ({:?} t-norm): \n{:?}", operator, intersection.memberships);
+    println!("\nComplement of Fuzzy Set 1: \n{:?}", complement1.memberships);
+    println!("\nComplement of Fuzzy Set 2: \n{:?}", complement2.memberships);
+    println!("\nAlpha-cut of Fuzzy Set 1 at {}: \n{:?}", alpha, alpha_cut1);
+    println!("\nAlpha-cut of Fuzzy Set 2 at {}: \n{:?}", alpha, alpha_cut2);
+    println!("\nStrong Alpha-cut of Fuzzy Set 1 at {}: \n{:?}", alpha, strong_alpha_cut1);
+    println!("\nStrong Alpha-cut of Fuzzy Set 2 at {}: \n{:?}", alpha, strong_alpha_cut2);
+    println!("\nCentroid of Fuzzy Set 1: \n{:?}", fuzzy_set1.centroid());
+    println!("\nCentroid of Fuzzy Set 2: \n{:?}", fuzzy_set2.centroid());
+    println!("\nMean of Maxima of Fuzzy Set 1: \n{:?}", fuzzy_set1.mean_of_maxima());
+    println!("\nMean of Maxima of Fuzzy Set 2: \n{:?}", fuzzy_set2.mean_of_maxima());
+
+    let file_path = "fuzzy_set_operations.csv";
+    let mut writer = Writer::from_path(file_path)?;
+
+    writer.write_record(["Standard Union", &format!("{:?}", standard_union.memberships)])?;
+    writer.write_record(["Standard Intersection", &format!("{:?}", standard_intersection.memberships)])?;
+    writer.write_record([&format!("Union ({:?})", operator), &format!("{:?}", union.memberships)])?;
+    writer.write_record([&format!("Intersection ({:?})", operator), &format!("{:?}", intersection.memberships)])?;
+    writer.write_record(["Complement of Fuzzy Set 1", &format!("{:?}", complement1.memberships)])?;
+    writer.write_record(["Complement of Fuzzy Set 2", &format!("{:?}", complement2.memberships)])?;
+    writer.write_record([&format!("Alpha-cut of Fuzzy Set 1 at {}", alpha), &format!("{:?}", alpha_cut1)])?;
+    writer.write_record([&format!("Alpha-cut of Fuzzy Set 2 at {}", alpha), &format!("{:?}", alpha_cut2)])?;
+    writer.write_record([&format!("Strong Alpha-cut of Fuzzy Set 1 at {}", alpha), &format!("{:?}", strong_alpha_cut1)])?;
+    writer.write_record([&format!("Strong Alpha-cut of Fuzzy Set 2 at {}", alpha), &format!("{:?}", strong_alpha_cut2)])?;
+    writer.write_record(["Centroid of Fuzzy Set 1", &format!("{:?}", fuzzy_set1.centroid())])?;
+    writer.write_record(["Centroid of Fuzzy Set 2", &format!("{:?}", fuzzy_set2.centroid())])?;
+    writer.write_record(["Mean of Maxima of Fuzzy Set 1", &format!("{:?}", fuzzy_set1.mean_of_maxima())])?;
+    writer.write_record(["Mean of Maxima of Fuzzy Set 2", &format!("{:?}", fuzzy_set2.mean_of_maxima())])?;
+
+    writer.flush()?;
+    println!("Results saved to {}", file_path);
+    Ok(())
+}
+
+
+// Endpoint of a real interval; infinite endpoints are always open
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Bound {
+    value: f64,
+    closed: bool,
+}
+
+// Interval of real numbers such as [0, 1), (2, inf) or {3}
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Interval {
+    lower: Bound,
+    upper: Bound,
+}
+
+impl Interval {
+    fn new(lower: f64, lower_closed: bool, upper: f64, upper_closed: bool) -> Result<Interval, String> {
+        if lower.is_nan() || upper.is_nan() {
+            return Err("Interval endpoints must be numbers".to_string());
+        }
+        Ok(Interval {
+            lower: Bound { value: lower, closed: lower_closed && lower.is_finite() },
+            upper: Bound { value: upper, closed: upper_closed && upper.is_finite() },
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.lower.value > self.upper.value
+            || (self.lower.value == self.upper.value && !(self.lower.closed && self.upper.closed))
+    }
+
+    fn contains(&self, x: f64) -> bool {
+        let above = x > self.lower.value || (x == self.lower.value && self.lower.closed);
+        let below = x < self.upper.value || (x == self.upper.value && self.upper.closed);
+        above && below
+    }
+
+    fn length(&self) -> f64 {
+        if self.is_empty() { 0.0 } else { self.upper.value - self.lower.value }
+    }
+
+    // Whether `other`, starting no earlier than this interval, overlaps or touches it
+    // so that the two merge into a single interval
+    fn joins(&self, other: &Interval) -> bool {
+        other.lower.value < self.upper.value
+            || (other.lower.value == self.upper.value && (other.lower.closed || self.upper.closed))
+    }
+
+    fn intersection(&self, other: &Interval) -> Interval {
+        // The larger lower bound and the smaller upper bound, preferring open ends on ties
+        let lower = if self.lower.value > other.lower.value
+            || (self.lower.value == other.lower.value && !self.lower.closed)
+        {
+            self.lower
+        } else {
+            other.lower
+        };
+        let upper = if self.upper.value < other.upper.value
+            || (self.upper.value == other.upper.value && !self.upper.closed)
+        {
+            self.upper
+        } else {
+            other.upper
+        };
+        Interval { lower, upper }
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.lower.value == self.upper.value {
+            return write!(f, "{{{}}}", self.lower.value);
+        }
+        write!(
+            f,
+            "{}{}, {}{}",
+            if self.lower.closed { '[' } else { '(' },
+            self.lower.value,
+            self.upper.value,
+            if self.upper.closed { ']' } else { ')' }
+        )
+    }
+}
+
+// Union of real intervals, kept as disjoint intervals sorted from left to right
+#[derive(Debug, Clone, PartialEq)]
+struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    // Drops empty intervals, sorts the rest and merges the ones that overlap or touch
+    fn new(intervals: Vec<Interval>) -> IntervalSet {
+        let mut intervals: Vec<Interval> = intervals.into_iter().filter(|interval| !interval.is_empty()).collect();
+        intervals.sort_by(|a, b| {
+            a.lower.value.total_cmp(&b.lower.value).then(b.lower.closed.cmp(&a.lower.closed))
+        });
+
+        let mut merged: Vec<Interval> = Vec::new();
+        for interval in intervals {
+            match merged.last_mut() {
+                Some(last) if last.joins(&interval) => {
+                    if interval.upper.value > last.upper.value
+                        || (interval.upper.value == last.upper.value && interval.upper.closed)
+                    {
+                        last.upper = interval.upper;
+                    }
+                }
+                _ => merged.push(interval),
+            }
+        }
+        IntervalSet { intervals: merged }
+    }
+
+    fn contains(&self, x: f64) -> bool {
+        self.intervals.iter().any(|interval| interval.contains(x))
+    }
+
+    fn union(&self, other: &IntervalSet) -> IntervalSet {
+        IntervalSet::new(self.intervals.iter().chain(&other.intervals).copied().collect())
+    }
+
+    fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let pieces = self
+            .intervals
+            .iter()
+            .flat_map(|a| other.intervals.iter().map(move |b| a.intersection(b)))
+            .collect();
+        IntervalSet::new(pieces)
+    }
+
+    // Everything on the real line outside the set: the gaps between consecutive intervals
+    fn complement(&self) -> IntervalSet {
+        let mut gaps = Vec::new();
+        let mut start = Bound { value: f64::NEG_INFINITY, closed: false };
+        for interval in &self.intervals {
+            let end = Bound { value: interval.lower.value, closed: !interval.lower.closed };
+            gaps.push(Interval { lower: start, upper: end });
+            start = Bound { value: interval.upper.value, closed: !interval.upper.closed };
+        }
+        gaps.push(Interval { lower: start, upper: Bound { value: f64::INFINITY, closed: false } });
+        IntervalSet::new(gaps)
+    }
+
+    fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        self.intersection(&other.complement())
+    }
+
+    // Total length of the intervals, infinite when the set is unbounded
+    fn measure(&self) -> f64 {
+        self.intervals.iter().map(Interval::length).sum()
+    }
+}
+
+impl fmt::Display for IntervalSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.intervals.is_empty() {
+            return write!(f, "∅");
+        }
+        let parts: Vec<String> = self.intervals.iter().map(|interval| interval.to_string()).collect();
+        write!(f, "{}", parts.join(" ∪ "))
+    }
+}
+
+// Parses unions like `[0,1) ∪ (2,inf) ∪ {5}`; `|` and `U` also mean union, `∅` is the empty set
+impl FromStr for IntervalSet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_endpoint = |text: &str| -> Result<f64, String> {
+            let text = text.trim().replace('∞', "inf");
+            text.parse().map_err(|_| format!("Invalid endpoint '{}'", text))
+        };
+
+        let mut intervals = Vec::new();
+        for part in s.split(['∪', '|', 'U']) {
+            let part = part.trim();
+            if part.is_empty() || part == "∅" || part == "{}" {
+                continue;
+            }
+
+            let mut chars = part.chars();
+            let (open, close) = (chars.next().unwrap_or(' '), chars.next_back().unwrap_or(' '));
+            let inner = chars.as_str();
+            if open == '{' && close == '}' {
+                let value = parse_endpoint(inner)?;
+                intervals.push(Interval::new(value, true, value, true)?);
+                continue;
+            }
+            if !matches!(open, '[' | '(') || !matches!(close, ']' | ')') {
+                return Err(format!("'{}' is not an interval", part));
+            }
+            let (lower, upper) = inner.split_once(',').ok_or_else(|| format!("'{}' needs two endpoints", part))?;
+            intervals.push(Interval::new(parse_endpoint(lower)?, open == '[', parse_endpoint(upper)?, close == ']')?);
+        }
+        Ok(IntervalSet::new(intervals))
+    }
+}
+
+fn read_interval_set() -> IntervalSet {
+    loop {
+        println!("Enter a union of intervals, e.g. [0,1) ∪ (2,inf):");
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).expect("Failed to read line");
+
+        match input.parse() {
+            Ok(interval_set) => return interval_set,
+            Err(message) => println!("Invalid input: {}", message),
+        }
+    }
+}
+
+fn interval_set_operations() -> Result<(), Box<dyn Error>> {
+    let interval_set1 = read_interval_set();
+    let interval_set2 = read_interval_set();
+    println!("Enter a number to test membership:");
+    let x = read_f64_input();
+
+    let union = interval_set1.union(&interval_set2);
+    let intersection = interval_set1.intersection(&interval_set2);
+    let difference = interval_set1.difference(&interval_set2);
+    let complement1 = interval_set1.complement();
+    let complement2 = interval_set2.complement();
+
+    println!("\nInterval Set 1: \n{}", interval_set1);
+    println!("\nInterval Set 2: \n{}", interval_set2);
+    println!("\nUnion: \n{}", union);
+    println!("\nIntersection: \n{}", intersection);
+    println!("\nDifference: \n{}", difference);
+    println!("\nComplement of Interval Set 1: \n{}", complement1);
+    println!("\nComplement of Interval Set 2: \n{}", complement2);
+    println!("\nMeasure of Interval Set 1: \n{}", interval_set1.measure());
+    println!("\nMeasure of Interval Set 2: \n{}", interval_set2.measure());
+    println!("\nIs {} in Interval Set 1? \n{}", x, interval_set1.contains(x));
+    println!("\nIs {} in Interval Set 2? \n{}", x, interval_set2.contains(x));
+
+    let file_path = "interval_set_operations.csv";
+    let mut writer = Writer::from_path(file_path)?;
+
+    writer.write_record(["Interval Set Operation", "Result"])?;
+    writer.write_record(["Union", &union.to_string()])?;
+    writer.write_record(["Intersection", &intersection.to_string()])?;
+    writer.write_record(["Difference", &difference.to_string()])?;
+    writer.write_record(["Complement of Interval Set 1", &complement1.to_string()])?;
+    writer.write_record(["Complement of Interval Set 2", &complement2.to_string()])?;
+    writer.write_record(["Measure of Interval Set 1", &interval_set1.measure().to_string()])?;
+    writer.write_record(["Measure of Interval Set 2", &interval_set2.measure().to_string()])?;
+    writer.write_record([&format!("Contains {} (Set 1)", x), &interval_set1.contains(x).to_string()])?;
+    writer.write_record([&format!("Contains {} (Set 2)", x), &interval_set2.contains(x).to_string()])?;
+
+    writer.flush()?;
+    println!("Results saved to {}", file_path);
+    Ok(())
+}
+
+
+// Every group of sets needs its own count, so the number of sets is kept small
+const MAX_INCLUSION_EXCLUSION_SETS: usize = 6;
+
+// Non-empty groups of set names in inclusion-exclusion order: single sets, then pairs, and so on
+fn inclusion_exclusion_groups(names: &Set<String>) -> impl Iterator<Item = Vec<String>> + '_ {
+    (1..=names.cardinality()).flat_map(move |k| names.subsets_of_size(k))
+}
+
+// Intersections of an odd number of sets are added, even ones subtracted
+fn inclusion_exclusion_sign(group: &[String]) -> i64 {
+    if group.len() % 2 == 1 { 1 } else { -1 }
+}
+
+// Size of the intersection of every group of sets; their signed sum is the size of the union
+fn inclusion_exclusion_terms<T: Ord + Hash + Clone>(sets: &BTreeMap<String, Set<T>>) -> Vec<(Vec<String>, i64)> {
+    let names: Set<String> = sets.keys().cloned().collect();
+    inclusion_exclusion_groups(&names)
+        .map(|group| {
+            let first = sets[&group[0]].clone();
+            let intersection = group[1..].iter().fold(first, |result, name| result.intersection(&sets[name]));
+            let size = intersection.cardinality() as i64;
+            (group, size)
+        })
+        .collect()
+}
+
+fn union_from_terms(terms: &[(Vec<String>, i64)]) -> i64 {
+    terms.iter().map(|(group, size)| inclusion_exclusion_sign(group) * size).sum()
+}
+
+// Finds the one intersection size that is not known, given the size of the union
+fn solve_missing_count(
+    names: &Set<String>,
+    known: &BTreeMap<Vec<String>, i64>,
+    union: i64,
+) -> Result<(Vec<String>, i64), String> {
+    let missing: Vec<Vec<String>> = inclusion_exclusion_groups(names).filter(|group| !known.contains_key(group)).collect();
+    if missing.len() != 1 {
+        return Err(format!("Exactly one count may be unknown, found {}", missing.len()));
+    }
+
+    let group = missing[0].clone();
+    let known_sum: i64 = known.iter().map(|(group, size)| inclusion_exclusion_sign(group) * size).sum();
+    let size = (union - known_sum) * inclusion_exclusion_sign(&group);
+    if size < 0 {
+        return Err(format!("The counts are inconsistent: |{}| would be {}", group.join(" ∩ "), size));
+    }
+    Ok((group, size))
+}
+
+// The number of elements in exactly the sets of a group follows from the intersection sizes by
+// inclusion-exclusion over the groups containing it; none of these may be negative for real sets
+fn check_inclusion_exclusion_terms(terms: &[(Vec<String>, i64)]) -> Result<(), String> {
+    for (group, _) in terms {
+        let region: i64 = terms
+            .iter()
+            .filter(|(other, _)| group.iter().all(|name| other.contains(name)))
+            .map(|(other, size)| if (other.len() - group.len()) % 2 == 1 { -size } else { *size })
+            .sum();
+        if region < 0 {
+            return Err(format!("The counts are inconsistent: {} elements would lie only in {}", region, group.join(" ∩ ")));
+        }
+    }
+    Ok(())
+}
+
+// Writes out the derivation, e.g. `|A ∪ B| = |A| + |B| - |A ∩ B| = 3 + 4 - 1 = 6`
+fn format_inclusion_exclusion(names: &Set<String>, terms: &[(Vec<String>, i64)]) -> String {
+    let mut symbols = String::new();
+    let mut values = String::new();
+    for (i, (group, size)) in terms.iter().enumerate() {
+        let sign = inclusion_exclusion_sign(group);
+        if i > 0 {
+            let operator = if sign > 0 { " + " } else { " - " };
+            symbols.push_str(operator);
+            values.push_str(operator);
+        }
+        symbols.push_str(&format!("|{}|", group.join(" ∩ ")));
+        values.push_str(&size.to_string());
+    }
+    format!("|{}| = {} = {} = {}", names.to_vec().join(" ∪ "), symbols, values, union_from_terms(terms))
+}
+
+// Reads a count, or nothing when the line is left empty
+fn read_optional_count(prompt: &str) -> Option<i64> {
+    loop {
+        println!("{} (leave empty if unknown):", prompt);
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).expect("Failed to read line");
+        let input = input.trim();
+        if input.is_empty() {
+            return None;
+        }
+        match input.parse() {
+            Ok(count) if count >= 0 => return Some(count),
+            _ => println!("Invalid input. Please enter a non-negative whole number."),
+        }
+    }
+}
+
+fn inclusion_exclusion_operations() -> Result<(), Box<dyn Error>> {
+    println!("Select how the sets are given:");
+    println!("1. By their elements");
+    println!("2. By the sizes of their intersections");
+
+    let mut choice = String::new();
+    io::stdin().read_line(&mut choice)?;
+
+    let (names, terms, universe_size) = match choice.trim() {
+        "1" => {
+            let mut sets: BTreeMap<String, Set<String>> = read_named_sets();
+            let universe_size = sets.remove("U").map(|universe| universe.cardinality() as i64);
+            let names: Set<String> = sets.keys().cloned().collect();
+            if names.cardinality() > MAX_INCLUSION_EXCLUSION_SETS {
+                return Err(format!("At most {} sets are supported", MAX_INCLUSION_EXCLUSION_SETS).into());
+            }
+            (names, inclusion_exclusion_terms(&sets), universe_size)
+        }
+        "2" => {
+            println!("Enter the set names (space-separated, e.g. A B C):");
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            let names: Set<String> = input.split_whitespace().map(|name| name.to_string()).collect();
+            if names.cardinality() > MAX_INCLUSION_EXCLUSION_SETS {
+                return Err(format!("At most {} sets are supported", MAX_INCLUSION_EXCLUSION_SETS).into());
+            }
+
+            let mut known = BTreeMap::new();
+            for group in inclusion_exclusion_groups(&names) {
+                if let Some(size) = read_optional_count(&format!("|{}|", group.join(" ∩ "))) {
+                    known.insert(group, size);
+                }
+            }
+            let union = read_optional_count(&format!("|{}|", names.to_vec().join(" ∪ ")));
+            let universe_size = read_optional_count("Number of elements in the universe");
+
+            let group_count = inclusion_exclusion_groups(&names).count();
+            if let Some(union) = union {
+                if known.len() < group_count {
+                    let (group, size) = solve_missing_count(&names, &known, union)?;
+                    println!("\nMissing count: \n|{}| = {}", group.join(" ∩ "), size);
+                    known.insert(group, size);
+                }
+            }
+            let terms: Vec<(Vec<String>, i64)> = inclusion_exclusion_groups(&names)
+                .map(|group| known.get(&group).map(|&size| (group.clone(), size)).ok_or(group))
+                .collect::<Result<_, _>>()
+                .map_err(|group| format!("|{}| is needed to count the union", group.join(" ∩ ")))?;
+            if let Some(union) = union {
+                if union_from_terms(&terms) != union {
+                    return Err(format!(
+                        "The counts are inconsistent: |{}| was given as {} but the other counts give {}",
+                        names.to_vec().join(" ∪ "),
+                        union,
+                        union_from_terms(&terms)
+                    )
+                    .into());
+                }
+            }
+            check_inclusion_exclusion_terms(&terms)?;
+            (names, terms, universe_size)
+        }
+        _ => return Err("Invalid choice".into()),
+    };
+
+    let union = union_from_terms(&terms);
+    if let Some(universe_size) = universe_size {
+        if universe_size < union {
+            return Err(format!("The universe has {} elements but the union has {}", universe_size, union).into());
+        }
+    }
+    println!("\nInclusion-exclusion terms:");
+    for (group, size) in &terms {
+        let sign = if inclusion_exclusion_sign(group) > 0 { '+' } else { '-' };
+        println!("{} |{}| = {}", sign, group.join(" ∩ "), size);
+    }
+    println!("\n{}", format_inclusion_exclusion(&names, &terms));
+    if let Some(universe_size) = universe_size {
+        println!("\nElements in none of the sets: \n{}", universe_size - union);
+    }
+
+    let file_path = "inclusion_exclusion.csv";
+    let mut writer = Writer::from_path(file_path)?;
+
+    writer.write_record(["Term", "Sign", "Size"])?;
+    for (group, size) in &terms {
+        let sign = if inclusion_exclusion_sign(group) > 0 { "+" } else { "-" };
+        writer.write_record([&format!("|{}|", group.join(" ∩ ")), sign, &size.to_string()])?;
+    }
+    writer.write_record([&format!("|{}|", names.to_vec().join(" ∪ ")), "=", &union.to_string()])?;
+    if let Some(universe_size) = universe_size {
+        writer.write_record(["In none of the sets", "=", &(universe_size - union).to_string()])?;
+    }
+
+    writer.flush()?;
+    println!("Results saved to {}", file_path);
+    Ok(())
+}
+
+
+// Probability measure on a finite sample space, given by the probability of each outcome.
+// Events are subsets of the sample space.
+#[derive(Debug, Clone, PartialEq)]
+struct ProbabilitySpace<T: Ord + Hash + Clone = i32> {
+    probabilities: BTreeMap<T, f64>,
+}
+
+impl<T: Ord + Hash + Clone> ProbabilitySpace<T> {
+    // Every outcome is equally likely
+    fn uniform(sample_space: &Set<T>) -> Result<Self, String> {
+        let weights = sample_space.elements.iter().map(|outcome| (outcome.clone(), 1.0)).collect();
+        ProbabilitySpace::weighted(weights)
+    }
+
+    // Outcomes are as likely as their weights, which are scaled to sum to 1
+    fn weighted(weights: BTreeMap<T, f64>) -> Result<Self, String> {
+        if weights.values().any(|&weight| !weight.is_finite() || weight < 0.0) {
+            return Err("Weights must be non-negative numbers".to_string());
+        }
+        let total: f64 = weights.values().sum();
+        if total == 0.0 {
+            return Err("The weights of the sample space must not all be zero".to_string());
+        }
+        let probabilities = weights.into_iter().map(|(outcome, weight)| (outcome, weight / total)).collect();
+        Ok(ProbabilitySpace { probabilities })
+    }
+
+    fn sample_space(&self) -> Set<T> {
+        self.probabilities.keys().cloned().collect()
+    }
+
+    // P(A), the sum of the probabilities of the outcomes in A
+    fn probability(&self, event: &Set<T>) -> Result<f64, String> {
+        event
+            .elements
+            .iter()
+            .map(|outcome| self.probabilities.get(outcome).ok_or_else(|| "The event is not a subset of the sample space".to_string()))
+            .sum()
+    }
+
+    // P(A ∪ B) = P(A) + P(B) - P(A ∩ B)
+    fn union_probability(&self, a: &Set<T>, b: &Set<T>) -> Result<f64, String> {
+        Ok(self.probability(a)? + self.probability(b)? - self.probability(&a.intersection(b))?)
+    }
+
+    // P(A | B) = P(A ∩ B) / P(B), undefined when B cannot happen
+    fn conditional_probability(&self, a: &Set<T>, b: &Set<T>) -> Result<f64, String> {
+        let probability_b = self.probability(b)?;
+        if probability_b == 0.0 {
+            return Err("The conditioning event has probability 0".to_string());
+        }
+        Ok(self.probability(&a.intersection(b))? / probability_b)
+    }
+
+    // P(A ∩ B) = P(A) · P(B), up to rounding
+    fn are_independent(&self, a: &Set<T>, b: &Set<T>) -> Result<bool, String> {
+        let joint = self.probability(&a.intersection(b))?;
+        Ok((joint - self.probability(a)? * self.probability(b)?).abs() < 1e-9)
+    }
+
+    // Bayes' rule: P(A | B) = P(B | A) · P(A) / P(B), where P(B) comes from the law of total
+    // probability over A and its complement. P(B | A) · P(A) is just P(B ∩ A), and likewise for ¬A
+    fn bayes(&self, a: &Set<T>, b: &Set<T>) -> Result<f64, String> {
+        let not_a = a.complement(&self.sample_space());
+        let numerator = self.probability(&a.intersection(b))?;
+        let evidence = numerator + self.probability(&not_a.intersection(b))?;
+        if evidence == 0.0 {
+            return Err("The conditioning event has probability 0".to_string());
+        }
+        Ok(numerator / evidence)
+    }
+
+    // E[X] = Σ X(ω) · P(ω)
+    fn expectation(&self, variable: &RandomVariable<T>) -> Result<f64, String> {
+        self.probabilities
+            .iter()
+            .map(|(outcome, probability)| Ok(variable.value(outcome)? * probability))
+            .sum()
+    }
+
+    // Var(X) = E[(X - E[X])²]
+    fn variance(&self, variable: &RandomVariable<T>) -> Result<f64, String> {
+        let mean = self.expectation(variable)?;
+        self.probabilities
+            .iter()
+            .map(|(outcome, probability)| Ok((variable.value(outcome)? - mean).powi(2) * probability))
+            .sum()
+    }
+}
+
+// Random variable: a number attached to each outcome of a sample space
+#[derive(Debug, Clone, PartialEq)]
+struct RandomVariable<T: Ord + Hash + Clone = i32> {
+    values: BTreeMap<T, f64>,
+}
+
+impl<T: Ord + Hash + Clone> RandomVariable<T> {
+    fn from_fn(sample_space: &Set<T>, f: impl Fn(&T) -> f64) -> Self {
+        RandomVariable { values: sample_space.elements.iter().map(|outcome| (outcome.clone(), f(outcome))).collect() }
+    }
+
+    fn value(&self, outcome: &T) -> Result<f64, String> {
+        self.values.get(outcome).copied().ok_or_else(|| "The random variable has no value for an outcome".to_string())
+    }
+}
+
+// Reads `outcome:number` entries, e.g. `1:0.5 2:0.25`
+fn read_outcome_values<T>(prompt: &str) -> BTreeMap<T, f64>
+where
+    T: Ord + FromStr,
+    T::Err: fmt::Debug,
+{
+    println!("{} (space-separated, each written as outcome:number, e.g. 1:0.5 2:0.25):", prompt);
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).expect("Failed to read line");
+    input
+        .split_whitespace()
+        .map(|entry| {
+            let (outcome, value) = entry.split_once(':').expect("Invalid entry");
+            (outcome.parse().expect("Invalid input"), value.parse().expect("Invalid input"))
+        })
+        .collect()
+}
+
+fn probability_operations<T>() -> Result<(), Box<dyn Error>>
+where
+    T: Ord + Hash + Clone + fmt::Debug + FromStr,
+    T::Err: fmt::Debug,
+{
+    println!("Are all outcomes equally likely? (true or false)");
+    let space = if read_boolean_input() {
+        println!("Sample space:");
+        ProbabilitySpace::uniform(&read_set())?
+    } else {
+        ProbabilitySpace::weighted(read_outcome_values("Enter the outcomes with their weights"))?
+    };
+    println!("Event A:");
+    let a: Set<T> = read_set();
+    println!("Event B:");
+    let b: Set<T> = read_set();
+    let values: BTreeMap<T, f64> = read_outcome_values("Enter the value of the random variable X for each outcome; outcomes left out are 0");
+    let variable = RandomVariable::from_fn(&space.sample_space(), |outcome| values.get(outcome).copied().unwrap_or(0.0));
+
+    let show = |result: Result<f64, String>| result.map_or_else(|e| e, |value| value.to_string());
+    let results = [
+        ("P(A)", show(space.probability(&a))),
+        ("P(B)", show(space.probability(&b))),
+        ("P(A ∪ B)", show(space.union_probability(&a, &b))),
+        ("P(A ∩ B)", show(space.probability(&a.intersection(&b)))),
+        ("P(A | B)", show(space.conditional_probability(&a, &b))),
+        ("P(B | A)", show(space.conditional_probability(&b, &a))),
+        ("P(A | B) by Bayes' rule", show(space.bayes(&a, &b))),
+        ("A and B independent", space.are_independent(&a, &b).map_or_else(|e| e, |independent| independent.to_string())),
+        ("E[X]", show(space.expectation(&variable))),
+        ("Var(X)", show(space.variance(&variable))),
+    ];
+
+    println!("\nProbabilities of the outcomes: \n{:?}", space.probabilities);
+    for (name, result) in &results {
+        println!("\n{}: \n{}", name, result);
+    }
+
+    let file_path = "probability_operations.csv";
+    let mut writer = Writer::from_path(file_path)?;
+
+    writer.write_record(["Probability Operation", "Result"])?;
+    for (outcome, probability) in &space.probabilities {
+        writer.write_record([&format!("P({:?})", outcome), &probability.to_string()])?;
+    }
+    for (name, result) in &results {
+        writer.write_record([*name, result.as_str()])?;
+    }
+
+    writer.flush()?;
+    println!("Results saved to {}", file_path);
+    Ok(())
+}
+
+
+// Union–find over the elements of a set. Elements are numbered in sorted order; each one
+// points towards the representative of its block, and `rank` bounds the height of each tree.
+#[derive(Debug, Clone)]
+struct DisjointSets<T: Ord + Hash + Clone = i32> {
+    elements: Vec<T>,
+    positions: BTreeMap<T, usize>,
+    parent: Vec<usize>,
+    rank: Vec<usize>,
+}
+
+impl<T: Ord + Hash + Clone> DisjointSets<T> {
+    // Every element starts in a block of its own
+    fn new(set: &Set<T>) -> Self {
+        let elements = set.to_vec();
+        let positions = elements.iter().cloned().enumerate().map(|(i, element)| (element, i)).collect();
+        let size = elements.len();
+        DisjointSets { elements, positions, parent: (0..size).collect(), rank: vec![0; size] }
+    }
+
+    fn position(&self, element: &T) -> Result<usize, String> {
+        self.positions.get(element).copied().ok_or_else(|| "The element is not in the set".to_string())
+    }
+
+    // Root of the tree holding position i; every position on the way is re-pointed at the root
+    fn find_root(&mut self, i: usize) -> usize {
+        let mut root = i;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut current = i;
+        while self.parent[current] != root {
+            let next = self.parent[current];
+            self.parent[current] = root;
+            current = next;
+        }
+        root
+    }
+
+    // Representative of the block containing the element
+    fn find(&mut self, element: &T) -> Result<T, String> {
+        let i = self.position(element)?;
+        let root = self.find_root(i);
+        Ok(self.elements[root].clone())
+    }
+
+    // Merges the blocks of the two elements, hanging the shorter tree under the taller one.
+    // Returns false when they were already in the same block.
+    fn union(&mut self, a: &T, b: &T) -> Result<bool, String> {
+        let root_a = self.position(a).map(|i| self.find_root(i))?;
+        let root_b = self.position(b).map(|i| self.find_root(i))?;
+        if root_a == root_b {
+            return Ok(false);
+        }
+        match self.rank[root_a].cmp(&self.rank[root_b]) {
+            Ordering::Less => self.parent[root_a] = root_b,
+            Ordering::Greater => self.parent[root_b] = root_a,
+            Ordering::Equal => {
+                self.parent[root_b] = root_a;
+                self.rank[root_a] += 1;
+            }
+        }
+        Ok(true)
+    }
+
+    fn connected(&mut self, a: &T, b: &T) -> Result<bool, String> {
+        Ok(self.find(a)? == self.find(b)?)
+    }
+
+    // Blocks of the partition, ordered by their smallest element
+    fn partition(&mut self) -> Vec<Set<T>> {
+        let mut blocks: BTreeMap<usize, Set<T>> = BTreeMap::new();
+        for i in 0..self.elements.len() {
+            let root = self.find_root(i);
+            blocks.entry(root).or_insert_with(Set::new).add(self.elements[i].clone());
+        }
+        let mut partition: Vec<Set<T>> = blocks.into_values().collect();
+        partition.sort_by(|a, b| a.elements.iter().next().cmp(&b.elements.iter().next()));
+        partition
+    }
+
+    fn block_count(&mut self) -> usize {
+        (0..self.elements.len()).filter(|&i| self.find_root(i) == i).count()
+    }
+}
+
+// Runs one `union a b`, `find a` or `connected a b` command and describes the outcome
+fn run_disjoint_sets_command<T>(disjoint_sets: &mut DisjointSets<T>, command: &str) -> Result<String, String>
+where
+    T: Ord + Hash + Clone + fmt::Debug + FromStr,
+{
+    let words: Vec<&str> = command.split_whitespace().collect();
+    let parse = |word: &str| word.parse::<T>().map_err(|_| format!("Invalid element: {}", word));
+    match words[..] {
+        ["union", a, b] => {
+            let (a, b) = (parse(a)?, parse(b)?);
+            if disjoint_sets.union(&a, &b)? {
+                Ok(format!("Merged the blocks of {:?} and {:?}", a, b))
+            } else {
+                Ok(format!("{:?} and {:?} are already in the same block", a, b))
+            }
+        }
+        ["find", a] => {
+            let a = parse(a)?;
+            Ok(format!("Representative of {:?}: {:?}", a, disjoint_sets.find(&a)?))
+        }
+        ["connected", a, b] => {
+            let (a, b) = (parse(a)?, parse(b)?);
+            Ok(format!("{:?} and {:?} in the same block: {}", a, b, disjoint_sets.connected(&a, &b)?))
+        }
+        _ => Err("Unknown command. Use `union a b`, `find a` or `connected a b`".to_string()),
+    }
+}
+
+fn disjoint_sets_operations<T>() -> Result<(), Box<dyn Error>>
+where
+    T: Ord + Hash + Clone + fmt::Debug + FromStr,
+    T::Err: fmt::Debug,
+{
+    let set: Set<T> = read_set();
+    let mut disjoint_sets = DisjointSets::new(&set);
+    let mut log = Vec::new();
+
+    println!("Enter commands: `union a b`, `find a` or `connected a b` (leave empty to finish):");
+    loop {
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        let command = input.trim();
+        if command.is_empty() {
+            break;
+        }
+        let outcome = run_disjoint_sets_command(&mut disjoint_sets, command).unwrap_or_else(|e| e);
+        println!("{}", outcome);
+        log.push((command.to_string(), outcome));
+    }
+
+    let partition = disjoint_sets.partition();
+    println!("\nPartition into {} blocks: \n{:?}", disjoint_sets.block_count(), partition);
+
+    let file_path = "disjoint_sets.csv";
+    let mut writer = Writer::from_path(file_path)?;
+
+    writer.write_record(["Command", "Result"])?;
+    for (command, outcome) in &log {
+        writer.write_record([command, outcome])?;
+    }
+    writer.write_record(["Number of Blocks", &disjoint_sets.block_count().to_string()])?;
+    for block in &partition {
+        writer.write_record(["Block", &format!("{:?}", block)])?;
+    }
+
+    writer.flush()?;
+    println!("Results saved to {}", file_path);
+    Ok(())
+}
+
+
+// Streams the tuples of A1 × A2 × … × An like an odometer: the last position changes fastest.
+// The product of no sets has exactly one tuple, the empty one.
+struct CartesianProduct<T> {
+    factors: Vec<Vec<T>>,
+    indices: Vec<usize>,
+    done: bool,
+}
+
+impl<T: Clone> CartesianProduct<T> {
+    fn new(factors: Vec<Vec<T>>) -> Self {
+        let done = factors.iter().any(|factor| factor.is_empty());
+        CartesianProduct { indices: vec![0; factors.len()], factors, done }
+    }
+}
+
+impl<T: Clone> Iterator for CartesianProduct<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.done {
+            return None;
+        }
+
+        let tuple = self.factors.iter().zip(&self.indices).map(|(factor, &i)| factor[i].clone()).collect();
+
+        // Advance the rightmost position that has not reached the end of its factor
+        match (0..self.factors.len()).rev().find(|&i| self.indices[i] + 1 < self.factors[i].len()) {
+            Some(i) => {
+                self.indices[i] += 1;
+                self.indices[i + 1..].iter_mut().for_each(|index| *index = 0);
+            }
+            None => self.done = true,
+        }
+
+        Some(tuple)
+    }
+}
+
+// Lazy product of any number of sets; tuples hold one element from each set, in order
+fn cartesian_product<T: Ord + Hash + Clone>(sets: &[Set<T>]) -> CartesianProduct<T> {
+    CartesianProduct::new(sets.iter().map(|set| set.to_vec()).collect())
+}
+
+// |A1 × A2 × … × An| = |A1| · |A2| · … · |An|, which quickly outgrows a machine integer
+fn cartesian_product_len<T: Ord + Hash + Clone>(sets: &[Set<T>]) -> BigUint {
+    sets.iter().fold(BigUint::from(1u32), |product, set| product * set.cardinality())
+}
+
+// Values indexed by the pairs of A × B, stored in a Matrix whose rows follow the elements
+// of A and whose columns follow the elements of B, both in sorted order
+#[derive(Debug)]
+struct ProductTable<T: Ord + Hash + Clone = i32, U: Ord + Hash + Clone = T> {
+    rows: Set<T>,
+    columns: Set<U>,
+    values: Matrix,
+}
+
+impl<T: Ord + Hash + Clone, U: Ord + Hash + Clone> ProductTable<T, U> {
+    fn new(rows: Set<T>, columns: Set<U>) -> Self {
+        let values = Matrix::new(rows.cardinality(), columns.cardinality(), vec![vec![0.0; columns.cardinality()]; rows.cardinality()]);
+        ProductTable { rows, columns, values }
+    }
+
+    // Table of f(a, b) for every pair of the product
+    fn from_fn(rows: Set<T>, columns: Set<U>, f: impl Fn(&T, &U) -> f64) -> Self {
+        let mut table = ProductTable::new(rows, columns);
+        for (i, a) in table.rows.elements.iter().enumerate() {
+            for (j, b) in table.columns.elements.iter().enumerate() {
+                table.values.data[i][j] = f(a, b);
+            }
+        }
+        table
+    }
+
+    // Matrix position of the pair (a, b), if both elements belong to the product
+    fn position(&self, a: &T, b: &U) -> Option<(usize, usize)> {
+        let i = self.rows.elements.iter().position(|row| row == a)?;
+        let j = self.columns.elements.iter().position(|column| column == b)?;
+        Some((i, j))
+    }
+
+    fn set(&mut self, a: &T, b: &U, value: f64) -> Result<(), String> {
+        let (i, j) = self.position(a, b).ok_or("The pair is not in the product of the table")?;
+        self.values.data[i][j] = value;
+        Ok(())
+    }
+}
+
+impl<T: Ord + Hash + Clone + fmt::Debug, U: Ord + Hash + Clone + fmt::Debug> ProductTable<T, U> {
+    fn display_table(&self) {
+        println!("Table:");
+        println!("\t{}", self.columns.elements.iter().map(|b| format!("{:?}", b)).collect::<Vec<_>>().join("\t"));
+        for (a, row) in self.rows.elements.iter().zip(&self.values.data) {
+            println!("{:?}\t{}", a, row.iter().map(|value| value.to_string()).collect::<Vec<_>>().join("\t"));
+        }
+    }
+}
+
+fn product_operations<T>() -> Result<(), Box<dyn Error>>
+where
+    T: Ord + Hash + Clone + fmt::Debug + FromStr,
+    T::Err: fmt::Debug,
+{
+    println!("Enter the number of sets in the product:");
+    let set_count: usize = read_element();
+    let sets: Vec<Set<T>> = (0..set_count).map(|_| read_set()).collect();
+
+    let cardinality = cartesian_product_len(&sets);
+    let symbols: Vec<String> = (1..=set_count).map(|i| format!("|Set {}|", i)).collect();
+    let sizes: Vec<String> = sets.iter().map(|set| set.cardinality().to_string()).collect();
+    println!("\nCardinality of the product: \n{} = {} = {}", symbols.join(" · "), sizes.join(" · "), cardinality);
+
+    // The product can be far too large to hold in memory, so write its tuples one at a time
+    let file_path = "cartesian_product.csv";
+    let mut writer = Writer::from_path(file_path)?;
+    let header: Vec<String> = (1..=set_count).map(|i| format!("Set {}", i)).collect();
+    writer.write_record(&header)?;
+    for tuple in cartesian_product(&sets) {
+        writer.write_record(tuple.iter().map(|element| format!("{:?}", element)))?;
+    }
+    writer.flush()?;
+    println!("Tuples saved to {}", file_path);
+
+    if set_count != 2 {
+        return Ok(());
+    }
+
+    println!("\nFill a table of values over Set 1 × Set 2:");
+    println!("1. Enter the values row by row");
+    println!("2. Mark the pairs of a relation from Set 1 to Set 2 with 1");
+    println!("3. Skip the table");
+
+    let mut choice = String::new();
+    io::stdin().read_line(&mut choice)?;
+
+    let table = match choice.trim() {
+        "1" => {
+            let mut table = ProductTable::new(sets[0].clone(), sets[1].clone());
+            for a in &sets[0].elements {
+                println!("Enter the {} values for row {:?} (space-separated):", sets[1].cardinality(), a);
+                let mut input = String::new();
+                io::stdin().read_line(&mut input)?;
+                let values: Vec<f64> = input.split_whitespace().map(|s| s.parse()).collect::<Result<_, _>>()?;
+                if values.len() != sets[1].cardinality() {
+                    return Err(format!("Expected {} values, got {}", sets[1].cardinality(), values.len()).into());
+                }
+                for (b, value) in sets[1].elements.iter().zip(values) {
+                    table.set(a, b, value)?;
+                }
+            }
+            table
+        }
+        "2" => {
+            println!("Enter the related pairs (space-separated, each written as a,b):");
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            let pairs: Set<(T, T)> = parse_pairs(&input)?;
+            if !pairs.is_subset(&sets[0].cartesian_product(&sets[1])) {
+                return Err("Every pair must take its first element from Set 1 and its second from Set 2".into());
+            }
+            ProductTable::from_fn(sets[0].clone(), sets[1].clone(), |a, b| {
+                if pairs.elements.contains(&(a.clone(), b.clone())) { 1.0 } else { 0.0 }
+            })
+        }
+        "3" => return Ok(()),
+        _ => return Err("Invalid choice".into()),
+    };
+    println!();
+    table.display_table();
+
+    let file_path = "product_table.csv";
+    let mut writer = Writer::from_path(file_path)?;
+    let mut header = vec![String::new()];
+    header.extend(table.columns.elements.iter().map(|b| format!("{:?}", b)));
+    writer.write_record(&header)?;
+    for (a, row) in table.rows.elements.iter().zip(&table.values.data) {
+        let mut record = vec![format!("{:?}", a)];
+        record.extend(row.iter().map(|value| value.to_string()));
+        writer.write_record(&record)?;
+    }
+    writer.flush()?;
+    println!("Table saved to {}", file_path);
+    Ok(())
+}
+
+
+// Streams the k-permutations of a set: ordered tuples of distinct elements, in lexicographic
+// order of positions
+struct Permutations<T> {
+    elements: Vec<T>,
+    indices: Vec<usize>,
+    done: bool,
+}
+
+impl<T: Clone> Permutations<T> {
+    fn new(elements: Vec<T>, k: usize) -> Self {
+        let done = k > elements.len();
+        Permutations { elements, indices: (0..k).collect(), done }
+    }
+}
+
+impl<T: Clone> Iterator for Permutations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.done {
+            return None;
+        }
+
+        let permutation = self.indices.iter().map(|&i| self.elements[i].clone()).collect();
+
+        // Find the rightmost position that can take a larger unused index, then refill the
+        // positions after it with the smallest unused indices
+        let n = self.elements.len();
+        let k = self.indices.len();
+        let step = (0..k).rev().find_map(|i| {
+            let used = &self.indices[..i];
+            (self.indices[i] + 1..n).find(|j| !used.contains(j)).map(|next| (i, next))
+        });
+        match step {
+            Some((i, next)) => {
+                self.indices[i] = next;
+                for position in i + 1..k {
+                    self.indices[position] = (0..n).find(|j| !self.indices[..position].contains(j)).unwrap();
+                }
+            }
+            None => self.done = true,
+        }
+
+        Some(permutation)
+    }
+}
+
+// Streams the k-combinations with repetition as non-decreasing tuples of positions
+struct CombinationsWithRepetition<T> {
+    elements: Vec<T>,
+    indices: Vec<usize>,
+    done: bool,
 }
 
-#[allow(unused_must_use)]
-impl Set {
-    fn new() -> Self {
-        Set { elements: Vec::new() }
+impl<T: Clone> CombinationsWithRepetition<T> {
+    fn new(elements: Vec<T>, k: usize) -> Self {
+        let done = k > 0 && elements.is_empty();
+        CombinationsWithRepetition { elements, indices: vec![0; k], done }
     }
+}
+
+impl<T: Clone> Iterator for CombinationsWithRepetition<T> {
+    type Item = Vec<T>;
 
-    fn add(&mut self, element: i32) {
-        if !self.elements.contains(&element) {
-            self.elements.push(element);
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.done {
+            return None;
         }
-    }
 
-    fn remove(&mut self, element: i32) {
-        if let Some(index) = self.elements.iter().position(|&x| x == element) {
-            self.elements.remove(index);
+        let combination = self.indices.iter().map(|&i| self.elements[i].clone()).collect();
+
+        // Increment the rightmost index that is not at the last element; the ones after it
+        // start again from its new value so the tuple stays non-decreasing
+        let n = self.elements.len();
+        match (0..self.indices.len()).rev().find(|&i| self.indices[i] + 1 < n) {
+            Some(i) => {
+                let next = self.indices[i] + 1;
+                self.indices[i..].iter_mut().for_each(|index| *index = next);
+            }
+            None => self.done = true,
         }
-    }
 
-    fn empty_set(&self) -> bool {
-        self.elements.is_empty()
+        Some(combination)
     }
+}
 
-    fn equal_sets(&self, other: &Set) -> bool {
-        self.elements.len() == other.elements.len() && self.elements.iter().all(|e| other.elements.contains(e))
-    }
+// Streams the permutations of a whole set that leave no element in its own position
+struct Derangements<T> {
+    elements: Vec<T>,
+    orderings: Permutations<usize>,
+}
 
-    fn unequal_sets(&self, other: &Set) -> bool {
-        !self.equal_sets(other)
+impl<T: Clone> Derangements<T> {
+    fn new(elements: Vec<T>) -> Self {
+        let n = elements.len();
+        Derangements { elements, orderings: Permutations::new((0..n).collect(), n) }
     }
+}
 
-    fn equivalent_sets(&self, other: &Set) -> bool {
-        self.equal_sets(other)
-    }
+impl<T: Clone> Iterator for Derangements<T> {
+    type Item = Vec<T>;
 
-    fn overlapping_sets(&self, other: &Set) -> bool {
-        self.elements.iter().any(|e| other.elements.contains(e))
+    fn next(&mut self) -> Option<Vec<T>> {
+        let ordering = self.orderings.find(|ordering| ordering.iter().enumerate().all(|(i, &j)| i != j))?;
+        Some(ordering.iter().map(|&j| self.elements[j].clone()).collect())
     }
+}
 
-    fn disjoint_sets(&self, other: &Set) -> bool {
-        !self.overlapping_sets(other)
-    }
+// n! = 1 · 2 · … · n
+fn factorial(n: usize) -> BigUint {
+    (1..=n).fold(BigUint::from(1u32), |product, i| product * i)
+}
 
-    // Check if this set is a subset of another set
-    fn is_subset(&self, other: &Set) -> bool {
-        self.elements.iter().all(|e| other.elements.contains(e))
+// Number of k-permutations of n elements, n! / (n - k)!
+fn permutation_count(n: usize, k: usize) -> BigUint {
+    if k > n {
+        return BigUint::default();
     }
+    (n - k + 1..=n).fold(BigUint::from(1u32), |product, i| product * i)
+}
 
-    // Check if this set is a superset of another set
-    fn is_superset(&self, other: &Set) -> bool {
-        other.is_subset(self)
+// n choose k; every partial product C(n, i) · (n - i) is divisible by i + 1, so the division is exact
+fn binomial(n: usize, k: usize) -> BigUint {
+    if k > n {
+        return BigUint::default();
     }
+    (0..k.min(n - k)).fold(BigUint::from(1u32), |result, i| result * (n - i) / (i + 1))
+}
 
-    // Calculate the power set of this set
-    fn power_set(&self) -> Vec<Vec<i32>> {
-        let mut power_set: Vec<Vec<i32>> = Vec::new();
-        let set_size = self.elements.len();
-        let total_subsets = 1 << set_size;
-
-        for i in 0..total_subsets {
-            let mut subset: Vec<i32> = Vec::new();
-            for j in 0..set_size {
-                if (i >> j) & 1 == 1 {
-                    subset.push(self.elements[j]);
-                }
-            }
-            power_set.push(subset);
-        }
-
-        power_set
+// Number of k-combinations with repetition of n elements, C(n + k - 1, k)
+fn combinations_with_repetition_count(n: usize, k: usize) -> BigUint {
+    if n == 0 {
+        return BigUint::from((k == 0) as u32);
     }
+    binomial(n + k - 1, k)
 }
 
-fn read_set() -> Set {
-    println!("Enter elements of the set (space-separated integers):");
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).expect("Failed to read line");
-    let elements: Vec<i32> = input
-        .trim()
-        .split_whitespace()
-        .map(|s| s.parse().expect("Invalid input"))
-        .collect();
+// (k1 + k2 + … + km)! / (k1! · k2! · … · km!), built as a product of binomials
+fn multinomial(parts: &[usize]) -> BigUint {
+    let mut total = 0;
+    parts.iter().fold(BigUint::from(1u32), |result, &part| {
+        total += part;
+        result * binomial(total, part)
+    })
+}
 
-    Set { elements }
+// n-th Catalan number, C(2n, n) / (n + 1)
+fn catalan(n: usize) -> BigUint {
+    binomial(2 * n, n) / (n + 1)
 }
 
-#[allow(unused_must_use)]
-fn set_operations() -> Result<(), Box<dyn Error>>{
-    let set1 = read_set();
-    let set2 = read_set();
+// Number of derangements of n elements, from D(n) = (n - 1) · (D(n - 1) + D(n - 2))
+fn derangement_count(n: usize) -> BigUint {
+    let (mut previous, mut current) = (BigUint::from(1u32), BigUint::default());
+    if n == 0 {
+        return previous;
+    }
+    for i in 2..=n {
+        let next = (previous + &current) * (i - 1);
+        previous = current;
+        current = next;
+    }
+    current
+}
 
-    println!("\n--------------Checking Sets----------------\n");
+fn combinatorics_operations() -> Result<(), Box<dyn Error>> {
+    println!("Select a combinatorics operation:");
+    println!("1. Arrange the elements of a set");
+    println!("2. Calculate factorials, binomials, multinomials and Catalan numbers");
 
-    println!("\nSet 1: \n{:?}", set1.elements);
-    println!("\nSet 2: \n{:?}", set2.elements);
+    let mut choice = String::new();
+    io::stdin().read_line(&mut choice)?;
 
-    println!("\nIs Set 1 empty? \n{}", set1.empty_set());
-    println!("\nIs Set 2 empty? \n{}", set2.empty_set());
+    match choice.trim() {
+        "1" => match read_element_type()? {
+            ElementType::Integer => arrangement_operations::<i32>(),
+            ElementType::Text => arrangement_operations::<String>(),
+            ElementType::Float => arrangement_operations::<OrderedF64>(),
+        },
+        "2" => counting_operations(),
+        _ => Err("Invalid choice".into()),
+    }
+}
 
-    println!("\nIs it equal Sets? \n{}", set1.equal_sets(&set2));
-    println!("\nIs it unequal Sets? \n{}", set1.unequal_sets(&set2));
-    println!("\nIs it equivalent Sets? \n{}", set1.equivalent_sets(&set2));
-    println!("\nIs it overlapping Sets? \n{}", set1.overlapping_sets(&set2));
-    println!("\nIs it disjoint Sets? \n{}", set1.disjoint_sets(&set2));
+fn arrangement_operations<T>() -> Result<(), Box<dyn Error>>
+where
+    T: Ord + Hash + Clone + fmt::Debug + FromStr,
+    T::Err: fmt::Debug,
+{
+    let set: Set<T> = read_set();
+    println!("Enter the number of elements to choose (k):");
+    let k: usize = read_element();
+    let n = set.cardinality();
 
-    println!("\nSet 1 is Subset of Set 2? \n{}", set1.is_subset(&set2));
-    println!("\nSet 1 is Superset of Set 2? \n{}", set1.is_superset(&set2));
+    println!("\nNumber of {}-permutations: \n{}", k, permutation_count(n, k));
+    println!("\nNumber of {}-combinations: \n{}", k, binomial(n, k));
+    println!("\nNumber of {}-combinations with repetition: \n{}", k, combinations_with_repetition_count(n, k));
+    println!("\nNumber of derangements: \n{}", derangement_count(n));
 
-    let power_set1 = set1.power_set();
-    let power_set2 = set2.power_set();
+    let file_path = "set_arrangements.csv";
+    let mut writer = Writer::from_path(file_path)?;
 
-    println!("\nPower Set of Set 1: \n{:?}", power_set1);
-    println!("\nPower Set of Set 2: \n{:?}", power_set2);
+    writer.write_record(["Arrangement", "Result"])?;
+    writer.write_record([&format!("Number of {}-Permutations", k), &permutation_count(n, k).to_string()])?;
+    writer.write_record([&format!("Number of {}-Combinations", k), &binomial(n, k).to_string()])?;
+    writer.write_record([
+        &format!("Number of {}-Combinations with Repetition", k),
+        &combinations_with_repetition_count(n, k).to_string(),
+    ])?;
+    writer.write_record(["Number of Derangements", &derangement_count(n).to_string()])?;
 
-    println!("-------------Adding and Removing Sets--------------");
+    write_listing(&mut writer, &format!("{}-Permutation", k), &permutation_count(n, k), set.permutations(k))?;
+    write_listing(&mut writer, &format!("{}-Combination", k), &binomial(n, k), set.subsets_of_size(k))?;
+    write_listing(
+        &mut writer,
+        &format!("{}-Combination with Repetition", k),
+        &combinations_with_repetition_count(n, k),
+        set.combinations_with_repetition(k),
+    )?;
+    write_listing(&mut writer, "Derangement", &derangement_count(n), set.derangements())?;
 
-    println!("Enter new set to add or remove the element:");
-    let mut set = read_set();
+    writer.flush()?;
+    println!("Arrangements saved to {}", file_path);
+    Ok(())
+}
 
-    // Add elements to the set
-    println!("Enter element to add:");
-    let mut add_element = String::new();
-    io::stdin().read_line(&mut add_element).expect("Failed to read line");
-    let add_element: i32 = add_element.trim().parse().expect("Invalid input");
-    set.add(add_element);
-    println!("Set after adding {}: {:?}", add_element, set.elements);
+fn counting_operations() -> Result<(), Box<dyn Error>> {
+    println!("Enter n:");
+    let n: usize = read_element();
+    println!("Enter k:");
+    let k: usize = read_element();
+    println!("Enter the group sizes for the multinomial (space-separated):");
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    let parts: Vec<usize> = input.split_whitespace().map(|s| s.parse()).collect::<Result<_, _>>()?;
+    let parts_text = parts.iter().map(|part| part.to_string()).collect::<Vec<_>>().join(", ");
 
-    // Remove an element from the set
-    println!("Enter element to remove:");
-    let mut remove_element = String::new();
-    io::stdin().read_line(&mut remove_element).expect("Failed to read line");
-    let remove_element: i32 = remove_element.trim().parse().expect("Invalid input");
-    set.remove(remove_element);
-    println!("Set after removing {}: {:?}", remove_element, set.elements);
+    println!("\n{}! = \n{}", n, factorial(n));
+    println!("\nC({}, {}) = \n{}", n, k, binomial(n, k));
+    println!("\nCatalan number C_{} = \n{}", n, catalan(n));
+    println!("\nMultinomial ({}) = \n{}", parts_text, multinomial(&parts));
 
-    // Open the CSV file for writing
-    let file_path = "set_operations.csv";
+    let file_path = "combinatorial_numbers.csv";
     let mut writer = Writer::from_path(file_path)?;
 
-    // Write set operation results to the CSV file
-    writer.write_record(&["Is Empty Set", &set1.empty_set().to_string()]);
-    writer.write_record(&["Is Equal Sets", &set1.equal_sets(&set2).to_string()]);
-    writer.write_record(&["Is Unequal Sets", &set1.unequal_sets(&set2).to_string()]);
-    writer.write_record(&["Is Equivalent Sets", &set1.equivalent_sets(&set2).to_string()]);
-    writer.write_record(&["Is Overlapping Sets", &set1.overlapping_sets(&set2).to_string()]);
-    writer.write_record(&["Is Disjoint Sets", &set1.disjoint_sets(&set2).to_string()]);
-    writer.write_record(&["Is Subset", &set1.is_subset(&set2).to_string()]);
-    writer.write_record(&["Is Superset", &set1.is_superset(&set2).to_string()]);
+    writer.write_record(["Quantity", "Result"])?;
+    writer.write_record([&format!("{}!", n), &factorial(n).to_string()])?;
+    writer.write_record([&format!("C({}, {})", n, k), &binomial(n, k).to_string()])?;
+    writer.write_record([&format!("Catalan C_{}", n), &catalan(n).to_string()])?;
+    writer.write_record([&format!("Multinomial ({})", parts_text), &multinomial(&parts).to_string()])?;
 
+    writer.flush()?;
     println!("Results saved to {}", file_path);
-    // Close the CSV writer
-    writer.flush();
-
     Ok(())
 }
 
@@ -458,10 +4504,10 @@ fn booleanlogic_operations() -> Result<(), Box<dyn Error>>{
     let file_path = "BooleanLogic_Operation.csv";
     let mut writer = Writer::from_path(file_path)?;
 
-    writer.write_record(&["Logical AND (a && b)", &bl.logical_and().to_string()])?;
-    writer.write_record(&["Logical OR (a || b)", &bl.logical_or().to_string()])?;
-    writer.write_record(&["Logical NOT for a (!a)", &bl.logical_not_a().to_string()])?;
-    writer.write_record(&["Logical NOT for b (!b)", &bl.logical_not_b().to_string()])?;
+    writer.write_record(["Logical AND (a && b)", &bl.logical_and().to_string()])?;
+    writer.write_record(["Logical OR (a || b)", &bl.logical_or().to_string()])?;
+    writer.write_record(["Logical NOT for a (!a)", &bl.logical_not_a().to_string()])?;
+    writer.write_record(["Logical NOT for b (!b)", &bl.logical_not_b().to_string()])?;
 
     writer.flush()?;
     Ok(())
@@ -544,27 +4590,28 @@ fn complex_operations() -> Result<(), Box<dyn Error>>{
     let file_path = "Complex_Operations.csv";
     let mut writer = Writer::from_path(file_path)?;
 
-    writer.write_record(&["Operation", "Real", "Imaginary"])?;
-    writer.write_record(&["Sum", &sum.real.to_string(), &sum.imag.to_string()])?;
-    writer.write_record(&["Difference", &difference.real.to_string(), &difference.imag.to_string()])?;
-    writer.write_record(&["Product", &product.real.to_string(), &product.imag.to_string()])?;
+    writer.write_record(["Operation", "Real", "Imaginary"])?;
+    writer.write_record(["Sum", &sum.real.to_string(), &sum.imag.to_string()])?;
+    writer.write_record(["Difference", &difference.real.to_string(), &difference.imag.to_string()])?;
+    writer.write_record(["Product", &product.real.to_string(), &product.imag.to_string()])?;
 
     writer.flush()?;
     println!("CSV output written successfully to 'Complex_Operations.csv'.");
     Ok(())
 }
 #[allow(unused_must_use)]
-
 fn main() -> Result<(), Box<dyn Error>>{
     loop {
         println!("Select your options: ");
         println!("1. Calculate Vector.");
         println!("2. Calculate Matrix.");
         println!("3. Calculate Set.");
-        println!("4. Calculate Boolean Logic");
-        println!("5. Calculate Complex");
-        println!("6. Return to the main menu");
-        println!("7. Exit Program.");
+        println!("4. Calculate Multiset.");
+        println!("5. Calculate Boolean Logic");
+        println!("6. Calculate Complex");
+        println!("7. Calculate Combinatorics");
+        println!("8. Return to the main menu");
+        println!("9. Exit Program.");
 
         let mut choice = String::new();
         io::stdin().read_line(&mut choice); // user input
@@ -574,10 +4621,12 @@ fn main() -> Result<(), Box<dyn Error>>{
         "1" => vector_operations(),
         "2" => matrix_operations(),
         "3" => set_operations(),
-        "4" => booleanlogic_operations(),
-        "5" => complex_operations(),
-        "6" => continue,
-        "7" => {
+        "4" => multiset_operations(),
+        "5" => booleanlogic_operations(),
+        "6" => complex_operations(),
+        "7" => combinatorics_operations(),
+        "8" => continue,
+        "9" => {
             println!("Exit the program. Goodbye!");
             break Ok(());
         }
@@ -598,7 +4647,7 @@ mod tests {
         let vector1 = Vector::new(1.0, 2.0, 3.0);
         let vector2 = Vector::new(4.0, 5.0, 6.0);
 
-        let result_addition = vector1.add(&vector2);
+        let result_addition = vector1 + vector2;
         assert_eq!(result_addition.x, 5.0);
         assert_eq!(result_addition.y, 7.0);
         assert_eq!(result_addition.z, 9.0);
@@ -613,8 +4662,226 @@ mod tests {
 
         let result_cross_product = vector1.cross_product(&vector2);
         assert_eq!(result_cross_product.x, -3.0);
-        assert_eq!(result_cross_product.y, -6.0);
+        assert_eq!(result_cross_product.y, 6.0);
         assert_eq!(result_cross_product.z, -3.0);
+
+        assert_eq!(vector1 + vector2, result_addition);
+        assert_eq!(vector1 - vector2, result_subtraction);
+        assert_eq!(vector1 + vector2 * 2.0, Vector::new(9.0, 12.0, 15.0));
+        assert_eq!(vector2 / 2.0, Vector::new(2.0, 2.5, 3.0));
+        assert_eq!(-vector1, Vector::new(-1.0, -2.0, -3.0));
+        let mut sum = Vector::default();
+        sum += vector1;
+        sum += vector2;
+        assert_eq!(sum, result_addition);
+        assert_eq!((vector1[0], vector1[1], vector1[2]), (1.0, 2.0, 3.0));
+        assert_eq!(Vector::new(1.5, -2.0, 0.0).to_string(), "(1.5, -2, 0)");
+    }
+
+    #[test]
+    fn test_vector_geometry_operations() {
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-12;
+        let x = Vector::new(1.0, 0.0, 0.0);
+        let y = Vector::new(0.0, 1.0, 0.0);
+        let v = Vector::new(3.0, 4.0, 0.0);
+        let zero = Vector::default();
+
+        assert_eq!(v.magnitude(), 5.0);
+        assert_eq!(v.normalize(), Ok(Vector::new(0.6, 0.8, 0.0)));
+        assert!(zero.normalize().is_err());
+
+        assert!(close(x.angle_between(&y).unwrap(), std::f64::consts::FRAC_PI_2));
+        assert!(close(x.angle_between_degrees(&y).unwrap(), 90.0));
+        assert_eq!(x.angle_between(&(x * 3.0)), Ok(0.0));
+        assert!(close(x.angle_between_degrees(&-x).unwrap(), 180.0));
+        assert!(x.angle_between(&zero).is_err());
+
+        assert_eq!(v.project_onto(&x), Ok(Vector::new(3.0, 0.0, 0.0)));
+        assert_eq!(v.reject_from(&x), Ok(Vector::new(0.0, 4.0, 0.0)));
+        assert_eq!(v.project_onto(&(x * 2.0)), Ok(Vector::new(3.0, 0.0, 0.0)));
+        assert!(v.project_onto(&zero).is_err());
+        assert_eq!(v.reflect_across(&y), Ok(Vector::new(3.0, -4.0, 0.0)));
+        assert_eq!(v.reflect_across(&(y * 5.0)), Ok(Vector::new(3.0, -4.0, 0.0)));
+        assert!(v.reflect_across(&zero).is_err());
+
+        assert_eq!(v.distance_to(&zero), 5.0);
+        assert_eq!(x.distance_to(&y), 2f64.sqrt());
+
+        assert!(x.is_parallel(&(x * -2.0), 1e-9));
+        assert!(!x.is_parallel(&v, 1e-9));
+        assert!(x.is_parallel(&Vector::new(1.0, 1e-12, 0.0), 1e-9));
+        assert!(x.is_orthogonal(&y, 1e-9));
+        assert!(!x.is_orthogonal(&v, 1e-9));
+        assert!(zero.is_parallel(&v, 1e-9) && zero.is_orthogonal(&v, 1e-9));
+    }
+
+    // Identities of the cross product, checked on random vectors up to a rounding error that
+    // scales with the size of the terms
+    mod vector_properties {
+        use super::*;
+        use proptest::prelude::*;
+
+        fn vector() -> impl Strategy<Value = Vector> {
+            (-100.0..100.0f64, -100.0..100.0f64, -100.0..100.0f64).prop_map(|(x, y, z)| Vector::new(x, y, z))
+        }
+
+        fn length(v: &Vector) -> f64 {
+            v.dot_product(v).sqrt()
+        }
+
+        fn assert_close(a: f64, b: f64, scale: f64) {
+            assert!((a - b).abs() <= 1e-9 * scale.max(1.0), "{} != {}", a, b);
+        }
+
+        proptest! {
+            #[test]
+            fn cross_product_is_orthogonal(a in vector(), b in vector()) {
+                let cross = a.cross_product(&b);
+                let scale = length(&a) * length(&a) * length(&b);
+                assert_close(a.dot_product(&cross), 0.0, scale);
+                assert_close(b.dot_product(&cross), 0.0, scale);
+            }
+
+            #[test]
+            fn cross_product_is_anticommutative(a in vector(), b in vector()) {
+                let ab = a.cross_product(&b);
+                let ba = b.cross_product(&a);
+                prop_assert_eq!((ab.x, ab.y, ab.z), (-ba.x, -ba.y, -ba.z));
+            }
+
+            #[test]
+            fn lagrange_identity(a in vector(), b in vector()) {
+                // |a × b|² = |a|²|b|² - (a · b)²
+                let cross = a.cross_product(&b);
+                let dot = a.dot_product(&b);
+                let scale = a.dot_product(&a) * b.dot_product(&b);
+                assert_close(cross.dot_product(&cross), scale - dot * dot, scale);
+            }
+
+            #[test]
+            fn bac_cab_rule(a in vector(), b in vector(), c in vector()) {
+                // a × (b × c) = b(a · c) - c(a · b)
+                let left = a.cross_product(&b.cross_product(&c));
+                let (ac, ab) = (a.dot_product(&c), a.dot_product(&b));
+                let right = Vector::new(b.x * ac - c.x * ab, b.y * ac - c.y * ab, b.z * ac - c.z * ab);
+                let scale = length(&a) * length(&b) * length(&c);
+                assert_close(left.x, right.x, scale);
+                assert_close(left.y, right.y, scale);
+                assert_close(left.z, right.z, scale);
+            }
+        }
+    }
+
+    #[test]
+    fn test_triple_product_operations() {
+        let x = Vector::new(1.0, 0.0, 0.0);
+        let y = Vector::new(0.0, 1.0, 0.0);
+        let z = Vector::new(0.0, 0.0, 1.0);
+        let a = Vector::new(1.0, 2.0, 3.0);
+        let b = Vector::new(4.0, 5.0, 6.0);
+        let c = Vector::new(-2.0, 1.0, 5.0);
+
+        assert_eq!(x.scalar_triple_product(&y, &z), 1.0);
+        assert_eq!(y.scalar_triple_product(&x, &z), -1.0);
+        assert_eq!(a.scalar_triple_product(&b, &c), -3.0);
+        assert_eq!(b.scalar_triple_product(&c, &a), -3.0);
+        assert_eq!(a.scalar_triple_product(&b, &c), a.cross_product(&b).dot_product(&c));
+
+        assert_eq!(x.vector_triple_product(&x, &y), -y);
+        assert_eq!(a.vector_triple_product(&b, &c), b * a.dot_product(&c) - c * a.dot_product(&b));
+
+        assert_eq!(a.parallelepiped_volume(&c, &b), 3.0);
+        assert_eq!(a.tetrahedron_volume(&b, &c), 0.5);
+        assert_eq!((x * 2.0).tetrahedron_volume(&(y * 3.0), &z), 1.0);
+
+        assert_eq!(Vector::are_coplanar(&[a, b, c], 1e-9), Ok(false));
+        assert_eq!(Vector::are_coplanar(&[a, b, a + b * 2.0], 1e-9), Ok(true));
+        assert_eq!(Vector::are_coplanar(&[x, y, Vector::default()], 1e-9), Ok(true));
+        let shift = Vector::new(10.0, -3.0, 7.0);
+        assert_eq!(Vector::are_coplanar(&[shift, shift + x, shift + y, shift + x * 2.0 - y], 1e-9), Ok(true));
+        assert_eq!(Vector::are_coplanar(&[shift, shift + x, shift + y, shift + z], 1e-9), Ok(false));
+        assert!(Vector::are_coplanar(&[x, y], 1e-9).is_err());
+    }
+
+    #[test]
+    fn test_vector_n_operations() {
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+        let a = VectorN::new(vec![1.0, 2.0, 3.0, 4.0]);
+        let b = VectorN::new(vec![4.0, 3.0, 2.0, 1.0]);
+        let short = VectorN::new(vec![1.0, 2.0]);
+
+        assert_eq!(a.dimension(), 4);
+        assert_eq!(a.add(&b), Ok(VectorN::new(vec![5.0; 4])));
+        assert_eq!(a.subtract(&b), Ok(VectorN::new(vec![-3.0, -1.0, 1.0, 3.0])));
+        assert_eq!(a.dot_product(&b), Ok(20.0));
+        assert_eq!(a.add(&short), Err(VectorError::DimensionMismatch { expected: 4, found: 2 }));
+        assert!(a.dot_product(&short).is_err());
+        assert_eq!(a.clone() * 2.0, VectorN::new(vec![2.0, 4.0, 6.0, 8.0]));
+        assert_eq!(-short.clone(), VectorN::new(vec![-1.0, -2.0]));
+        assert_eq!(a[3], 4.0);
+        assert_eq!(a.to_string(), "(1, 2, 3, 4)");
+
+        assert!(close(a.magnitude(), 30f64.sqrt()));
+        assert!(close(a.normalize().unwrap().magnitude(), 1.0));
+        assert_eq!(VectorN::new(vec![0.0; 4]).normalize(), Err(VectorError::ZeroVector));
+        let e1 = VectorN::new(vec![1.0, 0.0, 0.0, 0.0]);
+        let e2 = VectorN::new(vec![0.0, 1.0, 0.0, 0.0]);
+        assert!(close(e1.angle_between_degrees(&e2).unwrap(), 90.0));
+        assert_eq!(a.project_onto(&e1), Ok(VectorN::new(vec![1.0, 0.0, 0.0, 0.0])));
+        assert_eq!(a.reject_from(&e1), Ok(VectorN::new(vec![0.0, 2.0, 3.0, 4.0])));
+        assert_eq!(a.reflect_across(&e1), Ok(VectorN::new(vec![-1.0, 2.0, 3.0, 4.0])));
+        assert_eq!(a.distance_to(&a), Ok(0.0));
+        assert_eq!(a.is_parallel(&(a.clone() * -3.0), 1e-9), Ok(true));
+        assert_eq!(a.is_parallel(&b, 1e-9), Ok(false));
+        assert_eq!(e1.is_orthogonal(&e2, 1e-9), Ok(true));
+        assert!(e1.is_orthogonal(&short, 1e-9).is_err());
+
+        // Both the 3D route and the fixed-size `Vector` agree
+        let u = Vector::new(1.0, 2.0, 3.0);
+        let v = Vector::new(4.0, 5.0, 6.0);
+        assert_eq!(VectorN::from(u).cross_product(&VectorN::from(v)), Ok(VectorN::from(u.cross_product(&v))));
+        let generalized = VectorN::generalized_cross_product(&[VectorN::from(u), VectorN::from(v)]).unwrap();
+        assert!(close(generalized.distance_to(&VectorN::from(u.cross_product(&v))).unwrap(), 0.0));
+        assert_eq!(Vector::try_from(&VectorN::from(u)), Ok(u));
+        assert!(Vector::try_from(&a).is_err());
+        assert_eq!(
+            a.cross_product(&b),
+            Err(VectorError::UnsupportedDimension { operation: "cross product", dimension: 4 })
+        );
+        assert_eq!(a.cross_product(&b).unwrap_err().to_string(), "The cross product is not defined in 4 dimensions");
+
+        // The 7D cross product is orthogonal to both factors and satisfies the Lagrange identity
+        let p = VectorN::new(vec![1.0, -2.0, 0.5, 3.0, 0.0, 1.5, -1.0]);
+        let q = VectorN::new(vec![0.0, 1.0, 2.0, -1.0, 4.0, 0.5, 2.0]);
+        let cross = p.cross_product(&q).unwrap();
+        assert!(close(cross.dot_product(&p).unwrap(), 0.0));
+        assert!(close(cross.dot_product(&q).unwrap(), 0.0));
+        let dot = p.dot_product(&q).unwrap();
+        assert!(close(cross.magnitude().powi(2), p.magnitude().powi(2) * q.magnitude().powi(2) - dot * dot));
+        let basis = |i: usize| VectorN::new((0..7).map(|j| if i == j { 1.0 } else { 0.0 }).collect());
+        assert_eq!(basis(0).cross_product(&basis(1)), Ok(basis(2)));
+        assert_eq!(basis(3).cross_product(&basis(0)), Ok(-basis(4)));
+
+        // The generalized cross product of n - 1 vectors is orthogonal to all of them
+        let vectors = vec![a.clone(), b.clone(), VectorN::new(vec![0.0, 1.0, -1.0, 2.0])];
+        let normal = VectorN::generalized_cross_product(&vectors).unwrap();
+        for vector in &vectors {
+            assert!(close(normal.dot_product(vector).unwrap(), 0.0));
+        }
+        assert_eq!(VectorN::generalized_cross_product(std::slice::from_ref(&short)), Ok(VectorN::new(vec![-2.0, 1.0])));
+        assert_eq!(
+            VectorN::generalized_cross_product(&[a.clone(), b.clone()]),
+            Err(VectorError::WrongVectorCount { expected: 3, found: 2 })
+        );
+        assert_eq!(
+            VectorN::generalized_cross_product(&[a.clone(), short.clone()]),
+            Err(VectorError::DimensionMismatch { expected: 4, found: 2 })
+        );
+        assert_eq!(VectorN::generalized_cross_product(&[]), Ok(VectorN::new(vec![1.0])));
+
+        let matrix = Matrix::new(3, 3, vec![vec![0.0, 2.0, 1.0], vec![1.0, 1.0, 1.0], vec![2.0, 0.0, 3.0]]);
+        assert!(close(matrix.determinant().unwrap(), -4.0));
+        assert!(Matrix::new(1, 2, vec![vec![1.0, 2.0]]).determinant().is_err());
     }
 
     #[test]
@@ -648,33 +4915,705 @@ mod tests {
         set2.add(2);
         set2.add(3);
 
-        assert_eq!(set1.empty_set(), false);
-        assert_eq!(set2.empty_set(), false);
+        assert!(!set1.empty_set());
+        assert!(!set2.empty_set());
+
+        assert!(!set1.equal_sets(&set2));
+        assert!(set1.unequal_sets(&set2));
+        assert!(set1.equivalent_sets(&set2));
+        assert!(set1.overlapping_sets(&set2));
+        assert!(!set1.disjoint_sets(&set2));
+
+        assert!(!set1.is_subset(&set2));
+        assert!(!set1.is_superset(&set2));
+        assert!(!set1.is_proper_subset(&set2));
+        assert!(!set1.is_proper_superset(&set2));
 
-        assert_eq!(set1.equal_sets(&set2), false);
-        assert_eq!(set1.unequal_sets(&set2), true);
-        assert_eq!(set1.equivalent_sets(&set2), false);
-        assert_eq!(set1.overlapping_sets(&set2), true);
-        assert_eq!(set1.disjoint_sets(&set2), false);
+        assert_eq!(set1.cardinality(), 2);
+        assert_eq!(set1.bijection_to(&set2), Some(vec![(1, 2), (2, 3)]));
 
-        assert_eq!(set1.is_subset(&set2), false);
-        assert_eq!(set1.is_superset(&set2), false);
+        let mut set3 = set1.clone();
+        set3.add(3);
+        assert!(!set3.equivalent_sets(&set1));
+        assert!(set1.is_proper_subset(&set3));
+        assert!(set3.is_proper_superset(&set1));
+        assert!(!set1.is_proper_subset(&set1));
+        assert_eq!(set3.bijection_to(&set1), None);
 
-        let power_set1 = set1.power_set();
-        let power_set2 = set2.power_set();
+        let letters: Set<char> = "abc".chars().collect();
+        assert!(set3.equivalent_sets(&letters));
+        assert_eq!(set3.bijection_to(&letters), Some(vec![(1, 'a'), (2, 'b'), (3, 'c')]));
+
+        let power_set1: Vec<Vec<i32>> = set1.power_set_iter(SubsetOrder::Binary).collect();
+        let power_set2: Vec<Vec<i32>> = set2.power_set_iter(SubsetOrder::Binary).collect();
 
         assert_eq!(power_set1, vec![vec![], vec![1], vec![2], vec![1, 2]]);
         assert_eq!(power_set2, vec![vec![], vec![2], vec![3], vec![2, 3]]);
+
+        let mut universe = Set::new();
+        for e in 1..=5 {
+            universe.add(e);
+        }
+
+        assert_eq!(set1.union(&set2).to_vec(), vec![1, 2, 3]);
+        assert_eq!(set1.intersection(&set2).to_vec(), vec![2]);
+        assert_eq!(set1.difference(&set2).to_vec(), vec![1]);
+        assert_eq!(set1.symmetric_difference(&set2).to_vec(), vec![1, 3]);
+        assert_eq!(set1.complement(&universe).to_vec(), vec![3, 4, 5]);
+        assert_eq!(set2.complement(&universe).to_vec(), vec![1, 4, 5]);
+    }
+
+    #[test]
+    fn test_generic_set_operations() {
+        let words: Set<String> = ["pear", "apple", "pear"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(words.to_vec(), vec!["apple".to_string(), "pear".to_string()]);
+
+        let letters: Set<char> = "hello".chars().collect();
+        assert!(letters.is_superset(&"he".chars().collect()));
+
+        let pairs: Set<(i32, char)> = vec![(1, 'a'), (2, 'b')].into_iter().collect();
+        assert!(pairs.overlapping_sets(&vec![(2, 'b')].into_iter().collect()));
+
+        let floats: Set<OrderedF64> = vec![OrderedF64(0.5), OrderedF64(-1.0), OrderedF64(0.5)].into_iter().collect();
+        assert_eq!(floats.to_vec(), vec![OrderedF64(-1.0), OrderedF64(0.5)]);
+        assert_eq!("2.5".parse::<OrderedF64>().unwrap(), OrderedF64(2.5));
+
+        let large: Set<i32> = (0..300_000).collect();
+        let evens: Set<i32> = (0..300_000).step_by(2).collect();
+        assert!(evens.is_subset(&large));
+        assert!(large.unequal_sets(&evens));
+        assert_eq!(large.difference(&evens).elements.len(), 150_000);
+    }
+
+    #[test]
+    fn test_power_set_iterators() {
+        let set: Set<i32> = vec![1, 2, 3].into_iter().collect();
+
+        let gray: Vec<Vec<i32>> = set.power_set_iter(SubsetOrder::Gray).collect();
+        assert_eq!(gray, vec![vec![], vec![1], vec![1, 2], vec![2], vec![2, 3], vec![1, 2, 3], vec![1, 3], vec![3]]);
+
+        assert_eq!(set.subsets_of_size(2).collect::<Vec<_>>(), vec![vec![1, 2], vec![1, 3], vec![2, 3]]);
+        assert_eq!(set.subsets_of_size(0).collect::<Vec<_>>(), vec![Vec::<i32>::new()]);
+        assert_eq!(set.subsets_of_size(4).count(), 0);
+        assert_eq!(Set::<i32>::new().power_set_iter(SubsetOrder::Binary).collect::<Vec<_>>(), vec![Vec::<i32>::new()]);
+
+        let large: Set<i32> = (0..100).collect();
+        assert_eq!(large.power_set_len(), BigUint::from(1u32) << 100usize);
+        let first: Vec<Vec<i32>> = large.power_set_iter(SubsetOrder::Binary).take(3).collect();
+        assert_eq!(first, vec![vec![], vec![0], vec![1]]);
+        assert_eq!(large.subsets_of_size(99).next().map(|s| s.len()), Some(99));
+    }
+
+    #[test]
+    fn test_venn_diagram_operations() {
+        let set1: Set<i32> = vec![1, 2, 3, 5].into_iter().collect();
+        let set2: Set<i32> = vec![2, 3, 4, 6].into_iter().collect();
+        let set3: Set<i32> = vec![3, 5, 6, 7].into_iter().collect();
+
+        let venn = VennDiagram::new(&["A", "B"], &[&set1, &set2]).unwrap();
+        assert_eq!(
+            venn.region_counts(),
+            vec![("A ∩ B'".to_string(), 2), ("A ∩ B".to_string(), 2), ("A' ∩ B".to_string(), 2)]
+        );
+        let ascii = venn.to_ascii();
+        assert!(ascii.contains("| 1 5"));
+        assert!(ascii.contains("| 2 3 |"));
+        assert!(ascii.contains("4 6"));
+        assert_eq!(venn.to_svg().matches("<circle").count(), 2);
+
+        let venn = VennDiagram::new(&["A", "B", "C"], &[&set1, &set2, &set3]).unwrap();
+        let counts: Vec<usize> = venn.region_counts().into_iter().map(|(_, count)| count).collect();
+        assert_eq!(counts, vec![1, 1, 1, 1, 1, 1, 1]);
+        assert_eq!(venn.region_counts()[4].0, "A ∩ B ∩ C");
+        assert!(venn.to_ascii().contains("| 5 | 3  | 6 |"));
+        assert_eq!(venn.to_svg().matches("<circle").count(), 3);
+
+        assert!(VennDiagram::new(&["A"], &[&set1]).is_err());
+    }
+
+    #[test]
+    fn test_set_literal_operations() {
+        let set: Set<i32> = "{3, 1, 2, 2}".parse().unwrap();
+        assert_eq!(set.to_vec(), vec![1, 2, 3]);
+        assert_eq!(set.to_string(), "{1, 2, 3}");
+        assert_eq!(set.to_string().parse::<Set<i32>>(), Ok(set.clone()));
+        assert_eq!(" { 1 ,2,3 } ".parse::<Set<i32>>(), Ok(set));
+
+        let range: Set<i32> = "{1..10}".parse().unwrap();
+        assert_eq!(range.cardinality(), 10);
+        assert_eq!("{-2..0, 5, 7 .. 8}".parse::<Set<i32>>().unwrap().to_string(), "{-2, -1, 0, 5, 7, 8}");
+        assert_eq!("{3..1}".parse::<Set<i32>>(), Ok(Set::new()));
+
+        let nested: Set<Set<i32>> = "{{1},{2,3}, {}, {1..2}}".parse().unwrap();
+        assert_eq!(nested.cardinality(), 4);
+        assert_eq!(nested.to_string(), "{{}, {1}, {1, 2}, {2, 3}}");
+        assert_eq!(nested.to_string().parse::<Set<Set<i32>>>(), Ok(nested.clone()));
+        let deeper: Set<Set<Set<i32>>> = "{{{1}}, {}}".parse().unwrap();
+        assert_eq!(deeper.to_string().parse::<Set<Set<Set<i32>>>>(), Ok(deeper));
+
+        let words: Set<String> = "{pear, apple}".parse().unwrap();
+        assert_eq!(words.to_string(), "{apple, pear}");
+        assert_eq!(words.to_string().parse::<Set<String>>(), Ok(words));
+        let floats: Set<OrderedF64> = "{0.5, -1.25, 2}".parse().unwrap();
+        assert_eq!(floats.to_string(), "{-1.25, 0.5, 2}");
+        assert_eq!(floats.to_string().parse::<Set<OrderedF64>>(), Ok(floats));
+
+        assert_eq!("{}".parse::<Set<i32>>(), Ok(Set::new()));
+        assert_eq!("∅".parse::<Set<i32>>(), Ok(Set::new()));
+        assert_eq!(Set::<i32>::new().to_string(), "{}");
+        assert!("1, 2".parse::<Set<i32>>().is_err());
+        assert!("{1, x}".parse::<Set<i32>>().is_err());
+        assert!("{1,,2}".parse::<Set<i32>>().is_err());
+        assert!("{{1}, {2}".parse::<Set<Set<i32>>>().is_err());
+        assert!("{1}, {2}".parse::<Set<Set<i32>>>().is_err());
+        assert!("{0..1000000}".parse::<Set<i32>>().is_err());
+        assert!("{1..3000000000}".parse::<Set<i32>>().is_err());
+        assert!("{-9223372036854775808..9223372036854775807}".parse::<Set<i64>>().is_err());
+
+        // A comma inside an element reads back as two elements
+        let comma: Set<String> = vec!["a,b".to_string()].into_iter().collect();
+        assert_eq!(comma.to_string(), "{a,b}");
+        assert_eq!(comma.to_string().parse::<Set<String>>(), Ok(vec!["a".to_string(), "b".to_string()].into_iter().collect()));
+
+        assert_eq!(parse_set::<i32>("3 1 2"), Ok((1..=3).collect()));
+        assert_eq!(parse_set::<i32>("{1..3}"), Ok((1..=3).collect()));
+        assert!(parse_set::<i32>("1 two 3").is_err());
+    }
+
+    #[test]
+    fn test_set_expression_operations() {
+        let mut sets: BTreeMap<String, Set<i32>> = BTreeMap::new();
+        sets.insert("A".to_string(), vec![1, 2, 3].into_iter().collect());
+        sets.insert("B".to_string(), vec![3, 4].into_iter().collect());
+        sets.insert("C".to_string(), vec![1, 4, 5].into_iter().collect());
+        sets.insert("U".to_string(), (1..=6).collect());
+
+        let evaluate = |input: &str| SetExpr::parse(input).and_then(|expr| expr.evaluate(&sets)).map(|set| set.to_vec());
+
+        assert_eq!(evaluate("(A ∪ B) ∩ C'"), Ok(vec![2, 3]));
+        assert_eq!(evaluate("(A | B) & ~C"), Ok(vec![2, 3]));
+        assert_eq!(evaluate("A - (B & C)"), Ok(vec![1, 2, 3]));
+        assert_eq!(evaluate("A − B ∪ C"), Ok(vec![1, 2, 4, 5]));
+        assert_eq!(evaluate("A ∪ B ∩ C"), Ok(vec![1, 2, 3, 4]));
+        assert_eq!(evaluate("A Δ B"), Ok(vec![1, 2, 4]));
+        assert_eq!(evaluate("A''"), Ok(vec![1, 2, 3]));
+        assert_eq!(evaluate("(A ∪ B)'"), Ok(vec![5, 6]));
+        assert_eq!(evaluate("Aᶜ"), Ok(vec![4, 5, 6]));
+        assert_eq!(evaluate("(A ∪ B)ᶜ"), Ok(vec![5, 6]));
+        assert_eq!(evaluate("Aᶜᶜ"), Ok(vec![1, 2, 3]));
+        assert_eq!(SetExpr::parse("Aᶜᶜ").map(|expr| expr.to_string()), Ok("A''".to_string()));
+
+        assert_eq!(
+            SetExpr::parse("A ∪ B ∩ C'").map(|expr| expr.to_string()),
+            Ok("(A ∪ (B ∩ C'))".to_string())
+        );
+        assert!(evaluate("A ∪ D").is_err());
+        assert!(evaluate("(A ∪ B").is_err());
+        assert!(evaluate("A B").is_err());
+        assert!(evaluate("").is_err());
+    }
+
+    #[test]
+    fn test_set_identity_operations() {
+        let holds = |input: &str| SetIdentity::parse(input).map(|identity| identity.counterexample().is_none());
+
+        assert_eq!(holds("(A ∪ B)' = A' ∩ B'"), Ok(true));
+        assert_eq!(holds("A ∩ (B ∪ C) == (A ∩ B) ∪ (A ∩ C)"), Ok(true));
+        assert_eq!(holds("A - B ≡ A & ~B"), Ok(true));
+        assert_eq!(holds("A Δ B = (A ∪ B) − (A ∩ B)"), Ok(true));
+        assert_eq!(holds("A ∪ A' = U"), Ok(true));
+        assert_eq!(holds("A - B = B - A"), Ok(false));
+        assert_eq!(holds("Aᶜᶜ = A"), Ok(true));
+        assert_eq!(holds("(A ∪ B)ᶜ = Aᶜ ∩ Bᶜ"), Ok(true));
+        assert_eq!(holds("(A ∩ B)ᶜ = Aᶜ ∪ Bᶜ"), Ok(true));
+        assert_eq!(holds("(A ∩ B)ᶜ = Aᶜ ∩ Bᶜ"), Ok(false));
+
+        let identity = SetIdentity::parse("A ∪ (B ∩ C) = (A ∪ B) ∩ C").unwrap();
+        let sets = identity.counterexample().unwrap();
+        assert_ne!(identity.left.evaluate(&sets).unwrap().to_vec(), identity.right.evaluate(&sets).unwrap().to_vec());
+        assert_eq!(sets["A"].to_vec(), vec![1]);
+        assert!(sets["C"].empty_set());
+
+        assert!(SetIdentity::parse("A ∪ B").is_err());
+        assert!(SetIdentity::parse("= A").is_err());
+        assert!(SetIdentity::parse("A = B = C").is_err());
+    }
+
+    #[test]
+    fn test_relation_operations() {
+        let domain: Set<i32> = vec![1, 2, 3].into_iter().collect();
+        let pairs: Set<(i32, i32)> = vec![(1, 2), (2, 3)].into_iter().collect();
+        let relation = Relation::new(domain.clone(), pairs).unwrap();
+
+        assert!(!relation.is_reflexive());
+        assert!(!relation.is_symmetric());
+        assert!(relation.is_antisymmetric());
+        assert!(!relation.is_transitive());
+        assert!(!relation.is_equivalence());
+
+        let transitive = relation.transitive_closure();
+        assert_eq!(transitive.pairs.to_vec(), vec![(1, 2), (1, 3), (2, 3)]);
+        assert!(transitive.is_transitive());
+        assert_eq!(
+            transitive.to_matrix().data,
+            vec![vec![0.0, 1.0, 1.0], vec![0.0, 0.0, 1.0], vec![0.0, 0.0, 0.0]]
+        );
+
+        let equivalence = relation.reflexive_closure().symmetric_closure().transitive_closure();
+        assert!(equivalence.is_equivalence());
+        assert!(!equivalence.is_antisymmetric());
+        assert_eq!(equivalence.pairs.cardinality(), 9);
+
+        let cycle: Set<(i32, i32)> = vec![(1, 2), (2, 1)].into_iter().collect();
+        let cycle = Relation::new(domain.clone(), cycle).unwrap();
+        assert!(cycle.is_symmetric());
+        assert!(!cycle.is_transitive());
+        assert_eq!(cycle.transitive_closure().pairs.to_vec(), vec![(1, 1), (1, 2), (2, 1), (2, 2)]);
+
+        assert!(Relation::new(domain, vec![(1, 4)].into_iter().collect()).is_err());
+    }
+
+    #[test]
+    fn test_partition_operations() {
+        let domain: Set<i32> = (1..=5).collect();
+        let same_parity = domain
+            .elements
+            .iter()
+            .flat_map(|&a| domain.elements.iter().map(move |&b| (a, b)))
+            .filter(|(a, b)| (a - b) % 2 == 0)
+            .collect();
+        let relation = Relation::new(domain.clone(), same_parity).unwrap();
+        let classes: Vec<Vec<i32>> = relation.equivalence_classes().unwrap().iter().map(|c| c.to_vec()).collect();
+        assert_eq!(classes, vec![vec![1, 3, 5], vec![2, 4]]);
+        assert!(Relation::new(domain, Set::new()).unwrap().equivalence_classes().is_err());
+
+        let set: Set<i32> = vec![1, 2, 3].into_iter().collect();
+        let partitions: Vec<Vec<Vec<i32>>> =
+            set.partitions().map(|p| p.iter().map(|block| block.to_vec()).collect()).collect();
+        assert_eq!(
+            partitions,
+            vec![
+                vec![vec![1, 2, 3]],
+                vec![vec![1, 2], vec![3]],
+                vec![vec![1, 3], vec![2]],
+                vec![vec![1], vec![2, 3]],
+                vec![vec![1], vec![2], vec![3]],
+            ]
+        );
+        assert_eq!(Set::<i32>::new().partitions().count(), 1);
+        assert_eq!((1..=7).collect::<Set<i32>>().partitions().count(), 877);
+
+        assert_eq!(bell_number(0), BigUint::from(1u32));
+        assert_eq!(bell_number(5), BigUint::from(52u32));
+        assert_eq!(bell_number(30).to_string(), "846749014511809332450147");
+        assert_eq!(stirling_second_kind(5, 2), BigUint::from(15u32));
+        assert_eq!(stirling_second_kind(10, 4), BigUint::from(34105u32));
+        assert_eq!(stirling_second_kind(3, 0), BigUint::default());
+        assert_eq!(stirling_second_kind(3, 4), BigUint::default());
+    }
+
+    #[test]
+    fn test_mapping_operations() {
+        let domain: Set<i32> = vec![1, 2, 3].into_iter().collect();
+        let letters: Set<char> = "abc".chars().collect();
+
+        let f = Mapping::new(domain.clone(), letters.clone(), vec![(1, 'b'), (2, 'c'), (3, 'a')].into_iter().collect());
+        assert_eq!(parse_pairs::<i32, char>("1,b 2, c 3,a"), Err("Invalid pair `2,`, expected a,b".to_string()));
+        assert_eq!(parse_pairs("1,b 2,c 3,a"), Ok(f.pairs.clone()));
+        assert!(parse_pairs::<i32, char>("1-2").is_err());
+        assert!(parse_pairs::<i32, char>("x,a").is_err());
+        assert!(f.is_well_defined());
+        assert!(f.is_injective());
+        assert!(f.is_surjective());
+        assert!(f.is_bijective());
+        assert_eq!(f.apply(&2), Some(&'c'));
+
+        let f_inverse = f.inverse().unwrap();
+        assert_eq!(f_inverse.pairs.to_vec(), vec![('a', 3), ('b', 1), ('c', 2)]);
+        assert_eq!(f.compose(&f_inverse).unwrap().pairs.to_vec(), vec![(1, 1), (2, 2), (3, 3)]);
+
+        let constant = Mapping::new(letters.clone(), domain.clone(), vec![('a', 1), ('b', 1), ('c', 1)].into_iter().collect());
+        assert!(constant.is_well_defined());
+        assert!(!constant.is_injective());
+        assert!(!constant.is_surjective());
+        assert!(constant.inverse().is_err());
+        assert_eq!(f.compose(&constant).unwrap().pairs.to_vec(), vec![(1, 1), (2, 1), (3, 1)]);
+
+        let partial = Mapping::new(domain.clone(), letters.clone(), vec![(1, 'a'), (2, 'b')].into_iter().collect());
+        assert!(!partial.is_well_defined());
+        assert!(!partial.is_injective());
+        let duplicate = Mapping::new(domain.clone(), letters, vec![(1, 'a'), (1, 'b'), (2, 'b'), (3, 'c')].into_iter().collect());
+        assert!(!duplicate.is_well_defined());
+        assert!(duplicate.compose(&f_inverse).is_err());
+
+        let file_path = std::env::temp_dir().join("test_mapping_operations.csv");
+        fs::write(&file_path, "1,3\n2,1\n3,2\n").unwrap();
+        let from_csv: Mapping<i32> = Mapping::from_csv(domain.clone(), domain, file_path.to_str().unwrap()).unwrap();
+        assert!(from_csv.is_bijective());
+        assert_eq!(from_csv.apply(&1), Some(&3));
+        fs::remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_poset_operations() {
+        let divisors: Set<i32> = vec![1, 2, 3, 4, 6, 12].into_iter().collect();
+        let poset = Poset::divisibility(&divisors).unwrap();
+        assert_eq!(poset.minimal_elements().to_vec(), vec![1]);
+        assert_eq!(poset.maximal_elements().to_vec(), vec![12]);
+        assert_eq!(poset.least_element(), Some(1));
+        assert_eq!(poset.greatest_element(), Some(12));
+        assert_eq!(poset.join(&4, &6), Some(12));
+        assert_eq!(poset.meet(&4, &6), Some(2));
+        assert_eq!(poset.upper_bounds(&vec![2, 3].into_iter().collect()).to_vec(), vec![6, 12]);
+        assert_eq!(poset.lower_bounds(&vec![4, 6].into_iter().collect()).to_vec(), vec![1, 2]);
+        assert!(poset.is_lattice());
+        assert_eq!(poset.covering_pairs().len(), 7);
+        assert!(poset.to_dot().contains("n0 -> n1;"));
+
+        let no_top = Poset::divisibility(&vec![2, 3, 4].into_iter().collect()).unwrap();
+        assert_eq!(no_top.minimal_elements().to_vec(), vec![2, 3]);
+        assert_eq!(no_top.maximal_elements().to_vec(), vec![3, 4]);
+        assert_eq!(no_top.least_element(), None);
+        assert_eq!(no_top.join(&3, &4), None);
+        assert!(!no_top.is_lattice());
+        assert!(Poset::divisibility(&vec![-2, 2].into_iter().collect()).is_err());
+        // i32::MIN % -1 overflows, although -1 divides every integer
+        let extremes = Poset::divisibility(&vec![-1, i32::MIN].into_iter().collect()).unwrap();
+        assert!(extremes.leq(&-1, &i32::MIN));
+        assert_eq!(extremes.least_element(), Some(-1));
+
+        let subsets = Poset::subset_inclusion(&vec![1, 2].into_iter().collect()).unwrap();
+        assert!(Poset::subset_inclusion(&(1..=6).collect::<Set<i32>>()).is_err());
+        let a: Set<i32> = vec![1].into_iter().collect();
+        let b: Set<i32> = vec![2].into_iter().collect();
+        assert_eq!(subsets.join(&a, &b), Some(vec![1, 2].into_iter().collect()));
+        assert_eq!(subsets.meet(&a, &b), Some(Set::new()));
+        assert!(subsets.is_lattice());
+        assert!(subsets.to_dot().contains("label=\"{1, 2}\""));
+
+        let chain = Poset::from_pairs(vec!['a', 'b', 'c'].into_iter().collect(), vec![('a', 'b'), ('b', 'c')].into_iter().collect()).unwrap();
+        assert!(chain.leq(&'a', &'c'));
+        assert_eq!(chain.covering_pairs(), vec![('a', 'b'), ('b', 'c')]);
+        assert!(Poset::from_pairs(vec![1, 2].into_iter().collect(), vec![(1, 2), (2, 1)].into_iter().collect()).is_err());
+    }
+
+    #[test]
+    fn test_multiset_operations() {
+        let multiset1: Multiset<char> = "aabc".chars().collect();
+        let multiset2: Multiset<char> = "abbbd".chars().collect();
+
+        assert_eq!(multiset1.count(&'a'), 2);
+        assert_eq!(multiset1.count(&'d'), 0);
+        assert_eq!(multiset1.cardinality(), 4);
+
+        let counts = |multiset: Multiset<char>| multiset.counts.into_iter().collect::<Vec<_>>();
+        assert_eq!(counts(multiset1.union(&multiset2)), vec![('a', 2), ('b', 3), ('c', 1), ('d', 1)]);
+        assert_eq!(counts(multiset1.intersection(&multiset2)), vec![('a', 1), ('b', 1)]);
+        assert_eq!(counts(multiset1.sum(&multiset2)), vec![('a', 3), ('b', 4), ('c', 1), ('d', 1)]);
+        assert_eq!(counts(multiset1.difference(&multiset2)), vec![('a', 1), ('c', 1)]);
+        assert_eq!(counts(multiset2.difference(&multiset1)), vec![('b', 2), ('d', 1)]);
+
+        assert!(multiset1.intersection(&multiset2).is_submultiset(&multiset1));
+        assert!(!multiset1.is_submultiset(&multiset2));
+
+        assert_eq!(multiset1.to_set().to_vec(), vec!['a', 'b', 'c']);
+        let from_set = Multiset::from(multiset1.to_set());
+        assert_eq!(from_set.cardinality(), 3);
+
+        let mut multiset = multiset1.clone();
+        multiset.remove(&'a');
+        multiset.remove(&'c');
+        multiset.remove(&'z');
+        assert_eq!(counts(multiset), vec![('a', 1), ('b', 1)]);
+    }
+
+    #[test]
+    fn test_fuzzy_set_operations() {
+        let mut fuzzy_set1: FuzzySet<i32> = FuzzySet::new();
+        let mut fuzzy_set2: FuzzySet<i32> = FuzzySet::new();
+        for (element, degree) in [(1, 0.2), (2, 0.8), (3, 1.0)] {
+            fuzzy_set1.set_membership(element, degree).unwrap();
+        }
+        for (element, degree) in [(2, 0.5), (3, 0.5), (4, 0.4)] {
+            fuzzy_set2.set_membership(element, degree).unwrap();
+        }
+        assert!(fuzzy_set1.set_membership(5, 1.5).is_err());
+        assert_eq!(parse_fuzzy_set::<i32>("1:0.2 2:0.8 3:1").map(|fuzzy_set| fuzzy_set.memberships), Ok(fuzzy_set1.memberships.clone()));
+        assert!(parse_fuzzy_set::<i32>("1:1.5").is_err());
+        assert!(parse_fuzzy_set::<i32>("1").is_err());
+        assert!(parse_fuzzy_set::<i32>("x:0.5").is_err());
+
+        let degrees = |fuzzy_set: FuzzySet<i32>| fuzzy_set.memberships.into_iter().collect::<Vec<_>>();
+        assert_eq!(
+            degrees(fuzzy_set1.union(&fuzzy_set2, FuzzyOperator::Standard)),
+            vec![(1, 0.2), (2, 0.8), (3, 1.0), (4, 0.4)]
+        );
+        assert_eq!(
+            degrees(fuzzy_set1.intersection(&fuzzy_set2, FuzzyOperator::Standard)),
+            vec![(1, 0.0), (2, 0.5), (3, 0.5), (4, 0.0)]
+        );
+        assert_eq!(degrees(fuzzy_set1.intersection(&fuzzy_set2, FuzzyOperator::Algebraic))[1], (2, 0.4));
+        assert!((fuzzy_set1.union(&fuzzy_set2, FuzzyOperator::Algebraic).membership(&2) - 0.9).abs() < 1e-12);
+        assert!((fuzzy_set1.intersection(&fuzzy_set2, FuzzyOperator::Bounded).membership(&2) - 0.3).abs() < 1e-12);
+        assert_eq!(fuzzy_set1.intersection(&fuzzy_set2, FuzzyOperator::Drastic).membership(&2), 0.0);
+        assert_eq!(fuzzy_set1.intersection(&fuzzy_set2, FuzzyOperator::Drastic).membership(&3), 0.5);
+
+        // Parametric families reduce to the named pairs at their special parameters
+        for (a, b) in [(0.3, 0.6), (0.9, 0.2), (0.0, 1.0)] {
+            assert!((FuzzyOperator::Yager(1.0).t_norm(a, b) - FuzzyOperator::Bounded.t_norm(a, b)).abs() < 1e-12);
+            assert!((FuzzyOperator::Yager(200.0).t_norm(a, b) - a.min(b)).abs() < 1e-2);
+            assert!((FuzzyOperator::Hamacher(1.0).t_norm(a, b) - a * b).abs() < 1e-12);
+            assert!((FuzzyOperator::Hamacher(1.0).t_conorm(a, b) - (a + b - a * b)).abs() < 1e-12);
+        }
+
+        assert_eq!(degrees(fuzzy_set1.complement())[2], (3, 0.0));
+        assert_eq!(fuzzy_set1.alpha_cut(0.8).to_vec(), vec![2, 3]);
+        assert_eq!(fuzzy_set1.strong_alpha_cut(0.8).to_vec(), vec![3]);
+
+        assert!((fuzzy_set1.centroid().unwrap() - 2.4).abs() < 1e-12);
+        assert_eq!(fuzzy_set2.mean_of_maxima(), Some(2.5));
+        assert_eq!(FuzzySet::<i32>::new().centroid(), None);
+    }
+
+    #[test]
+    fn test_interval_set_operations() {
+        let parse = |input: &str| input.parse::<IntervalSet>().unwrap();
+        let interval_set1 = parse("[0,1) ∪ (2,inf)");
+        let interval_set2 = parse("[0.5, 3] | {10}");
+
+        assert_eq!(interval_set1.to_string(), "[0, 1) ∪ (2, inf)");
+        assert_eq!(parse("(1,3) ∪ [0,1] ∪ [5,4]").to_string(), "[0, 3)");
+        assert_eq!(parse("[0,1) ∪ (1,2]").to_string(), "[0, 1) ∪ (1, 2]");
+        assert_eq!(parse("[-∞, 0]").to_string(), "(-inf, 0]");
+        assert_eq!(parse("∅").to_string(), "∅");
+
+        assert!(interval_set1.contains(0.0));
+        assert!(!interval_set1.contains(1.0));
+        assert!(!interval_set1.contains(2.0));
+        assert!(interval_set1.contains(1e300));
+
+        assert_eq!(interval_set1.union(&interval_set2).to_string(), "[0, inf)");
+        assert_eq!(interval_set1.intersection(&interval_set2).to_string(), "[0.5, 1) ∪ (2, 3] ∪ {10}");
+        assert_eq!(interval_set1.complement().to_string(), "(-inf, 0) ∪ [1, 2]");
+        assert_eq!(interval_set2.complement().to_string(), "(-inf, 0.5) ∪ (3, 10) ∪ (10, inf)");
+        assert_eq!(interval_set1.difference(&interval_set2).to_string(), "[0, 0.5) ∪ (3, 10) ∪ (10, inf)");
+        assert_eq!(interval_set1.complement().complement(), interval_set1);
+        assert_eq!(parse("∅").complement().to_string(), "(-inf, inf)");
+
+        assert_eq!(interval_set2.measure(), 2.5);
+        assert_eq!(interval_set1.measure(), f64::INFINITY);
+
+        assert!("[0,1".parse::<IntervalSet>().is_err());
+        assert!("[a,1]".parse::<IntervalSet>().is_err());
+        assert!("[1]".parse::<IntervalSet>().is_err());
+    }
+
+    #[test]
+    fn test_inclusion_exclusion_operations() {
+        let mut sets: BTreeMap<String, Set<i32>> = BTreeMap::new();
+        sets.insert("A".to_string(), vec![1, 2, 3, 4].into_iter().collect());
+        sets.insert("B".to_string(), vec![3, 4, 5].into_iter().collect());
+        sets.insert("C".to_string(), vec![4, 5, 6, 7].into_iter().collect());
+
+        let terms = inclusion_exclusion_terms(&sets);
+        let sizes: Vec<i64> = terms.iter().map(|(_, size)| *size).collect();
+        assert_eq!(sizes, vec![4, 3, 4, 2, 1, 2, 1]);
+        assert_eq!(terms[3].0, vec!["A".to_string(), "B".to_string()]);
+        assert_eq!(union_from_terms(&terms), 7);
+
+        let names: Set<String> = sets.keys().cloned().collect();
+        assert_eq!(
+            format_inclusion_exclusion(&names, &terms[..]),
+            "|A ∪ B ∪ C| = |A| + |B| + |C| - |A ∩ B| - |A ∩ C| - |B ∩ C| + |A ∩ B ∩ C| = 4 + 3 + 4 - 2 - 1 - 2 + 1 = 7"
+        );
+
+        let mut known: BTreeMap<Vec<String>, i64> = terms.iter().cloned().collect();
+        let triple = known.remove(&vec!["A".to_string(), "B".to_string(), "C".to_string()]).unwrap();
+        assert_eq!(solve_missing_count(&names, &known, 7), Ok((vec!["A".to_string(), "B".to_string(), "C".to_string()], triple)));
+        let pair = known.remove(&vec!["A".to_string(), "C".to_string()]).unwrap();
+        assert!(solve_missing_count(&names, &known, 7).is_err());
+        known.insert(vec!["A".to_string(), "C".to_string()], pair);
+        known.insert(vec!["A".to_string(), "B".to_string(), "C".to_string()], triple);
+        known.remove(&vec!["B".to_string()]);
+        assert_eq!(solve_missing_count(&names, &known, 7), Ok((vec!["B".to_string()], 3)));
+        assert!(solve_missing_count(&names, &known, 0).is_err());
+
+        assert_eq!(check_inclusion_exclusion_terms(&terms), Ok(()));
+        let group = |names: &str| names.split(' ').map(|name| name.to_string()).collect::<Vec<_>>();
+        // |A ∩ B| = 5 is more than |B| = 3 allows
+        let too_big = vec![(group("A"), 6), (group("B"), 3), (group("A B"), 5)];
+        assert!(check_inclusion_exclusion_terms(&too_big).is_err());
+        // Each pair shares 2 elements but none is in all three, so A would need at least 4 elements
+        let crowded = vec![
+            (group("A"), 3),
+            (group("B"), 3),
+            (group("C"), 3),
+            (group("A B"), 2),
+            (group("A C"), 2),
+            (group("B C"), 2),
+            (group("A B C"), 0),
+        ];
+        assert!(check_inclusion_exclusion_terms(&crowded).is_err());
+    }
+
+    #[test]
+    fn test_probability_operations() {
+        let die: Set<i32> = (1..=6).collect();
+        let space = ProbabilitySpace::uniform(&die).unwrap();
+        let even: Set<i32> = vec![2, 4, 6].into_iter().collect();
+        let low: Set<i32> = vec![1, 2].into_iter().collect();
+        let high: Set<i32> = vec![4, 5, 6].into_iter().collect();
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-12;
+
+        assert!(close(space.probability(&even).unwrap(), 0.5));
+        assert!(close(space.probability(&die).unwrap(), 1.0));
+        assert_eq!(space.probability(&Set::new()), Ok(0.0));
+        assert!(space.probability(&vec![7].into_iter().collect()).is_err());
+        assert!(close(space.union_probability(&even, &low).unwrap(), 4.0 / 6.0));
+        assert!(close(space.conditional_probability(&even, &high).unwrap(), 2.0 / 3.0));
+        assert!(space.conditional_probability(&even, &Set::new()).is_err());
+        assert_eq!(space.are_independent(&even, &low), Ok(true));
+        assert_eq!(space.are_independent(&even, &high), Ok(false));
+        for (a, b) in [(&even, &high), (&low, &even), (&high, &low)] {
+            assert!(close(space.bayes(a, b).unwrap(), space.conditional_probability(a, b).unwrap()));
+        }
+        // The probabilities of ten equal outcomes do not sum to exactly 1
+        let ten: Set<i32> = (1..=10).collect();
+        let ten_space = ProbabilitySpace::uniform(&ten).unwrap();
+        assert!(close(ten_space.bayes(&ten, &low).unwrap(), 1.0));
+        // P(A) = 0 makes P(A | B) = 0 without dividing by P(A)
+        let loaded = ProbabilitySpace::weighted(BTreeMap::from([(1, 1.0), (2, 0.0), (3, 1.0)])).unwrap();
+        let two: Set<i32> = vec![2].into_iter().collect();
+        assert_eq!(loaded.bayes(&two, &low), Ok(0.0));
+        assert!(loaded.bayes(&two, &two).is_err());
+
+        let value = RandomVariable::from_fn(&die, |&face| face as f64);
+        assert!(close(space.expectation(&value).unwrap(), 3.5));
+        assert!(close(space.variance(&value).unwrap(), 35.0 / 12.0));
+        assert!(space.expectation(&RandomVariable { values: BTreeMap::from([(1, 1.0)]) }).is_err());
+
+        let coin = ProbabilitySpace::weighted(BTreeMap::from([("H".to_string(), 3.0), ("T".to_string(), 1.0)])).unwrap();
+        assert_eq!(coin.probabilities["H"], 0.75);
+        let heads: Set<String> = vec!["H".to_string()].into_iter().collect();
+        assert_eq!(coin.probability(&heads), Ok(0.75));
+        let payout = RandomVariable { values: BTreeMap::from([("H".to_string(), 1.0), ("T".to_string(), -1.0)]) };
+        assert_eq!(coin.expectation(&payout), Ok(0.5));
+        assert_eq!(coin.variance(&payout), Ok(0.75));
+        assert!(ProbabilitySpace::weighted(BTreeMap::from([(1, -1.0), (2, 2.0)])).is_err());
+        assert!(ProbabilitySpace::<i32>::uniform(&Set::new()).is_err());
+    }
+
+    #[test]
+    fn test_disjoint_sets_operations() {
+        let set: Set<i32> = (1..=6).collect();
+        let mut disjoint_sets = DisjointSets::new(&set);
+        assert_eq!(disjoint_sets.block_count(), 6);
+        assert_eq!(disjoint_sets.find(&4), Ok(4));
+
+        assert_eq!(disjoint_sets.union(&1, &2), Ok(true));
+        assert_eq!(disjoint_sets.union(&3, &4), Ok(true));
+        assert_eq!(disjoint_sets.union(&2, &4), Ok(true));
+        assert_eq!(disjoint_sets.union(&1, &3), Ok(false));
+        assert!(disjoint_sets.union(&1, &7).is_err());
+        assert!(disjoint_sets.find(&0).is_err());
+
+        assert_eq!(disjoint_sets.connected(&1, &4), Ok(true));
+        assert_eq!(disjoint_sets.connected(&1, &5), Ok(false));
+        assert_eq!(disjoint_sets.find(&3), disjoint_sets.find(&2));
+        assert_eq!(disjoint_sets.block_count(), 3);
+        assert_eq!(disjoint_sets.partition(), vec![(1..=4).collect(), vec![5].into_iter().collect(), vec![6].into_iter().collect()]);
+
+        // Union by rank keeps every tree at most log2(n) high, and path compression flattens it
+        assert!(disjoint_sets.rank.iter().all(|&rank| rank <= 2));
+        let root = disjoint_sets.find_root(0);
+        assert!((0..4).all(|i| disjoint_sets.parent[i] == root));
+
+        assert_eq!(run_disjoint_sets_command(&mut disjoint_sets, "union 5 6"), Ok("Merged the blocks of 5 and 6".to_string()));
+        assert_eq!(run_disjoint_sets_command(&mut disjoint_sets, "connected 6 5"), Ok("6 and 5 in the same block: true".to_string()));
+        assert!(run_disjoint_sets_command(&mut disjoint_sets, "find x").is_err());
+        assert!(run_disjoint_sets_command(&mut disjoint_sets, "merge 1 2").is_err());
+        assert_eq!(disjoint_sets.partition().len(), 2);
+    }
+
+    #[test]
+    fn test_cartesian_product_operations() {
+        let a: Set<i32> = vec![1, 2].into_iter().collect();
+        let b: Set<i32> = vec![3, 4, 5].into_iter().collect();
+        let c: Set<i32> = vec![6].into_iter().collect();
+
+        let pairs = a.cartesian_product(&b);
+        assert_eq!(pairs.cardinality(), 6);
+        assert_eq!(pairs.to_vec()[..2], [(1, 3), (1, 4)]);
+
+        let sets = vec![a.clone(), b.clone(), c.clone()];
+        let tuples: Vec<Vec<i32>> = cartesian_product(&sets).collect();
+        assert_eq!(tuples.len(), 6);
+        assert_eq!(tuples[0], vec![1, 3, 6]);
+        assert_eq!(tuples[1], vec![1, 4, 6]);
+        assert_eq!(tuples[5], vec![2, 5, 6]);
+        assert_eq!(cartesian_product_len(&sets), BigUint::from(6u32));
+
+        assert_eq!(cartesian_product(&[a.clone(), Set::new()]).count(), 0);
+        assert_eq!(cartesian_product::<i32>(&[]).collect::<Vec<_>>(), vec![Vec::<i32>::new()]);
+        assert_eq!(cartesian_product_len(&vec![b.clone(); 40]), BigUint::from(3u32).pow(40));
+
+        let mut table = ProductTable::from_fn(a.clone(), b.clone(), |x, y| (x * y) as f64);
+        assert_eq!(table.values.data, vec![vec![3.0, 4.0, 5.0], vec![6.0, 8.0, 10.0]]);
+        assert_eq!(table.position(&2, &4), Some((1, 1)));
+        assert_eq!(table.position(&3, &4), None);
+        table.set(&1, &5, -1.0).unwrap();
+        assert_eq!(table.values.data[0][2], -1.0);
+        assert!(table.set(&1, &6, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_combinatorics_operations() {
+        let set: Set<i32> = vec![1, 2, 3].into_iter().collect();
+
+        let permutations: Vec<Vec<i32>> = set.permutations(2).collect();
+        assert_eq!(permutations, vec![vec![1, 2], vec![1, 3], vec![2, 1], vec![2, 3], vec![3, 1], vec![3, 2]]);
+        assert_eq!(set.permutations(3).count(), 6);
+        assert_eq!(set.permutations(3).last(), Some(vec![3, 2, 1]));
+        assert_eq!(set.permutations(0).collect::<Vec<_>>(), vec![Vec::<i32>::new()]);
+        assert_eq!(set.permutations(4).count(), 0);
+
+        let multisets: Vec<Vec<i32>> = set.combinations_with_repetition(2).collect();
+        assert_eq!(multisets, vec![vec![1, 1], vec![1, 2], vec![1, 3], vec![2, 2], vec![2, 3], vec![3, 3]]);
+        assert_eq!(set.combinations_with_repetition(5).count(), 21);
+        assert_eq!(Set::<i32>::new().combinations_with_repetition(2).count(), 0);
+
+        assert_eq!(set.derangements().collect::<Vec<_>>(), vec![vec![2, 3, 1], vec![3, 1, 2]]);
+        let five: Set<i32> = (1..=5).collect();
+        assert_eq!(five.derangements().count(), 44);
+
+        for k in 0..7 {
+            assert_eq!(BigUint::from(five.permutations(k).count()), permutation_count(5, k));
+            assert_eq!(BigUint::from(five.subsets_of_size(k).count()), binomial(5, k));
+            assert_eq!(BigUint::from(five.combinations_with_repetition(k).count()), combinations_with_repetition_count(5, k));
+        }
+        for n in 0..7 {
+            let set: Set<usize> = (0..n).collect();
+            assert_eq!(BigUint::from(set.derangements().count()), derangement_count(n));
+        }
+
+        assert_eq!(factorial(0), BigUint::from(1u32));
+        assert_eq!(factorial(25).to_string(), "15511210043330985984000000");
+        assert_eq!(binomial(100, 50).to_string(), "100891344545564193334812497256");
+        assert_eq!(binomial(3, 5), BigUint::default());
+        assert_eq!(multinomial(&[2, 1, 1]), BigUint::from(12u32));
+        assert_eq!(multinomial(&[]), BigUint::from(1u32));
+        assert_eq!((0..8).map(|n| catalan(n).to_string()).collect::<Vec<_>>(), vec!["1", "1", "2", "5", "14", "42", "132", "429"]);
+        assert_eq!(combinations_with_repetition_count(0, 0), BigUint::from(1u32));
     }
 
     #[test]
     fn test_boolean_logic_operations() {
         let bl = BooleanLogic::new(true, false);
 
-        assert_eq!(bl.logical_and(), false);
-        assert_eq!(bl.logical_or(), true);
-        assert_eq!(bl.logical_not_a(), false);
-        assert_eq!(bl.logical_not_b(), true);
+        assert!(!bl.logical_and());
+        assert!(bl.logical_or());
+        assert!(!bl.logical_not_a());
+        assert!(bl.logical_not_b());
     }
 
     #[test]